@@ -0,0 +1,344 @@
+//! Derive macros for `dolt-dieselrs-getting-started`.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, punctuated::Punctuated, Attribute, Data, DeriveInput, Expr, Fields,
+    GenericArgument, Ident, Meta, Path, PathArguments, Token, Type,
+};
+
+/// Generates the row type of a `dolt_diff_<table>` system table for a model.
+///
+/// The model needs `#[diesel(table_name = ...)]`; every field becomes a
+/// `to_<field>` and a `from_<field>` column, typed after the `table!` column.
+/// Add `#[dolt_diff(name = ...)]` to pick the struct name (the default is
+/// `<Model>Diff`) and `#[dolt_diff(tabled)]` for a `tabled::Tabled` impl.
+/// Rows load by column name; the field order only sets the column order of
+/// the `tabled` display.
+/// The generated code expects `diesel` and `chrono` (and `tabled`) to be
+/// dependencies of the crate using it.
+///
+/// ```ignore
+/// #[derive(Queryable, Selectable, DoltDiff)]
+/// #[diesel(table_name = employees)]
+/// #[dolt_diff(name = DoltDiffEmployeesEntry)]
+/// pub struct Employee {
+///     pub id: i32,
+///     pub last_name: String,
+///     pub first_name: String,
+/// }
+/// ```
+#[proc_macro_derive(DoltDiff, attributes(dolt_diff))]
+pub fn derive_dolt_diff(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match dolt_diff(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Generates the row type of a `dolt_conflicts_<table>` system table for a model.
+///
+/// Works like [`DoltDiff`](derive@DoltDiff), with `base_`, `our_` and
+/// `their_` columns, configured through `#[dolt_conflict(...)]` (the default
+/// name is `<Model>Conflict`). The row type implements
+/// `dolt_dieselrs_getting_started::conflicts::ConflictRow`.
+#[proc_macro_derive(DoltConflict, attributes(dolt_conflict))]
+pub fn derive_dolt_conflict(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match dolt_conflict(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+struct ModelColumn {
+    column: Ident,
+    ty: Type,
+}
+
+struct RowField {
+    name: Ident,
+    ty: Type,
+    sql_type: TokenStream2,
+    optional: bool,
+}
+
+impl RowField {
+    fn text(name: Ident) -> Self {
+        RowField {
+            name,
+            ty: syn::parse_quote!(Option<String>),
+            sql_type: quote!(diesel::sql_types::Nullable<diesel::sql_types::Text>),
+            optional: true,
+        }
+    }
+
+    fn column(prefix: &str, table: &Path, c: &ModelColumn) -> Self {
+        let column = &c.column;
+        RowField {
+            name: format_ident!("{}_{}", prefix, column),
+            ty: c.ty.clone(),
+            sql_type: quote!(<<#table::#column as diesel::Expression>::SqlType as diesel::sql_types::IntoNullable>::Nullable),
+            optional: true,
+        }
+    }
+}
+
+fn dolt_diff(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let (table, columns) = model_columns(&input, "DoltDiff")?;
+    let (name, tabled) = row_options(&input, "dolt_diff", "Diff")?;
+
+    /*
+    Dolt's layout: to_*, to_commit, to_commit_date, from_*, ... Rows load
+    by name, so this order only shows in the tabled display, where the
+    table's own columns follow the model's fields.
+    */
+    let mut fields = Vec::new();
+    for side in ["to", "from"] {
+        fields.extend(columns.iter().map(|c| RowField::column(side, &table, c)));
+        fields.push(RowField::text(format_ident!("{}_commit", side)));
+        fields.push(RowField {
+            name: format_ident!("{}_commit_date", side),
+            ty: syn::parse_quote!(Option<chrono::NaiveDateTime>),
+            sql_type: quote!(diesel::sql_types::Nullable<diesel::sql_types::Timestamp>),
+            optional: true,
+        });
+    }
+    fields.push(RowField::text(Ident::new("diff_type", Span::call_site())));
+
+    let doc = format!(
+        "A row of `dolt_diff_{}`, generated from [`{}`].",
+        table_name(&table),
+        input.ident
+    );
+    let row = row_struct(&input, &name, &doc, &fields);
+    let tabled_impl = tabled.then(|| tabled_impl(&name, &fields));
+
+    Ok(quote! {
+        #row
+        #tabled_impl
+    })
+}
+
+fn dolt_conflict(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let (table, columns) = model_columns(&input, "DoltConflict")?;
+    let (name, tabled) = row_options(&input, "dolt_conflict", "Conflict")?;
+
+    let mut fields = vec![RowField::text(Ident::new(
+        "from_root_ish",
+        Span::call_site(),
+    ))];
+    fields.extend(columns.iter().map(|c| RowField::column("base", &table, c)));
+    for side in ["our", "their"] {
+        fields.extend(columns.iter().map(|c| RowField::column(side, &table, c)));
+        fields.push(RowField::text(format_ident!("{}_diff_type", side)));
+    }
+    fields.push(RowField {
+        name: Ident::new("dolt_conflict_id", Span::call_site()),
+        ty: syn::parse_quote!(String),
+        sql_type: quote!(diesel::sql_types::Text),
+        optional: false,
+    });
+
+    let table_name = table_name(&table);
+    let doc = format!(
+        "A row of `dolt_conflicts_{table_name}`, generated from [`{}`].",
+        input.ident
+    );
+    let row = row_struct(&input, &name, &doc, &fields);
+    let tabled_impl = tabled.then(|| tabled_impl(&name, &fields));
+
+    Ok(quote! {
+        #row
+
+        impl dolt_dieselrs_getting_started::conflicts::ConflictRow for #name {
+            const TABLE: &'static str = #table_name;
+
+            fn conflict_id(&self) -> &str {
+                &self.dolt_conflict_id
+            }
+        }
+
+        #tabled_impl
+    })
+}
+
+fn row_struct(input: &DeriveInput, name: &Ident, doc: &str, fields: &[RowField]) -> TokenStream2 {
+    let vis = &input.vis;
+    let struct_fields = fields.iter().map(|field| {
+        let RowField {
+            name, ty, sql_type, ..
+        } = field;
+        quote! {
+            #[diesel(sql_type = #sql_type)]
+            pub #name: #ty
+        }
+    });
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Debug, PartialEq, diesel::QueryableByName)]
+        #vis struct #name {
+            #(#struct_fields,)*
+        }
+    }
+}
+
+fn tabled_impl(name: &Ident, fields: &[RowField]) -> TokenStream2 {
+    let length = fields.len();
+    let headers = fields.iter().map(|field| field.name.to_string());
+    let cells = fields.iter().map(|field| {
+        let name = &field.name;
+        if field.optional {
+            quote! {
+                match &self.#name {
+                    Some(value) => std::borrow::Cow::Owned(value.to_string()),
+                    None => std::borrow::Cow::Borrowed("_"),
+                }
+            }
+        } else {
+            quote!(std::borrow::Cow::Owned(self.#name.to_string()))
+        }
+    });
+
+    quote! {
+        impl tabled::Tabled for #name {
+            const LENGTH: usize = #length;
+
+            fn fields(&self) -> Vec<std::borrow::Cow<'_, str>> {
+                vec![#(#cells),*]
+            }
+
+            fn headers() -> Vec<std::borrow::Cow<'static, str>> {
+                vec![#(std::borrow::Cow::Borrowed(#headers)),*]
+            }
+        }
+    }
+}
+
+/// The model's `table!` and its columns, each typed as an `Option`.
+fn model_columns(input: &DeriveInput, derive: &str) -> syn::Result<(Path, Vec<ModelColumn>)> {
+    let model = &input.ident;
+
+    let table = diesel_option(&input.attrs, "table_name")?
+        .and_then(|expr| match expr {
+            Expr::Path(path) => Some(path.path),
+            _ => None,
+        })
+        .ok_or_else(|| {
+            syn::Error::new_spanned(model, format!("{derive} needs #[diesel(table_name = ...)]"))
+        })?;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    model,
+                    format!("{derive} needs named fields"),
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                model,
+                format!("{derive} only works on structs"),
+            ))
+        }
+    };
+
+    let mut columns = Vec::new();
+    for field in fields {
+        let column = match diesel_option(&field.attrs, "column_name")? {
+            Some(Expr::Path(path)) if path.path.get_ident().is_some() => {
+                path.path.get_ident().cloned().unwrap()
+            }
+            Some(other) => {
+                return Err(syn::Error::new_spanned(
+                    other,
+                    format!("{derive} expects a plain column name"),
+                ))
+            }
+            None => field.ident.clone().unwrap(),
+        };
+
+        columns.push(ModelColumn {
+            column,
+            ty: option_of(&field.ty),
+        });
+    }
+
+    Ok((table, columns))
+}
+
+/// The `name = ...` and `tabled` options of the `#[<attr>(...)]` attributes.
+fn row_options(input: &DeriveInput, attr: &str, suffix: &str) -> syn::Result<(Ident, bool)> {
+    let mut name = format_ident!("{}{}", input.ident, suffix);
+    let mut tabled = false;
+
+    for a in input.attrs.iter().filter(|a| a.path().is_ident(attr)) {
+        a.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                name = meta.value()?.parse()?;
+                Ok(())
+            } else if meta.path.is_ident("tabled") {
+                tabled = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `name = ...` or `tabled`"))
+            }
+        })?;
+    }
+
+    Ok((name, tabled))
+}
+
+fn table_name(table: &Path) -> String {
+    table
+        .segments
+        .last()
+        .map(|segment| segment.ident.to_string())
+        .unwrap_or_default()
+}
+
+/// The value of `key` in the `#[diesel(...)]` attributes, if any.
+fn diesel_option(attrs: &[Attribute], key: &str) -> syn::Result<Option<Expr>> {
+    for attr in attrs.iter().filter(|a| a.path().is_ident("diesel")) {
+        let options = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        for option in options {
+            if let Meta::NameValue(option) = option {
+                if option.path.is_ident(key) {
+                    return Ok(Some(option.value));
+                }
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// `ty` if it already is an `Option`, `Option<ty>` otherwise.
+fn option_of(ty: &Type) -> Type {
+    if let Type::Path(type_path) = ty {
+        if is_option(&type_path.path) {
+            return ty.clone();
+        }
+    }
+
+    syn::parse_quote!(Option<#ty>)
+}
+
+fn is_option(path: &Path) -> bool {
+    path.segments.last().is_some_and(|segment| {
+        segment.ident == "Option"
+            && matches!(
+                &segment.arguments,
+                PathArguments::AngleBracketed(args)
+                    if matches!(args.args.first(), Some(GenericArgument::Type(_)))
+            )
+    })
+}
@@ -0,0 +1,251 @@
+//! `AS OF` time travel in the Diesel query DSL.
+//!
+//! Dolt can read any table as it was at a commit, branch, tag or point in
+//! time with `SELECT ... FROM employees AS OF 'rev'`. [`AsOfDsl::as_of`]
+//! wraps a table so it renders that way, while still working with `.select`,
+//! `.filter`, joins and `Selectable` models:
+//!
+//! ```no_run
+//! # use diesel::prelude::*;
+//! # use dolt_dieselrs_getting_started::{as_of::AsOfDsl, models::TeamEmployee, schema::*};
+//! # fn run(conn: &mut MysqlConnection) -> QueryResult<()> {
+//! let before_merge = employees::table
+//!     .as_of("modify_data")
+//!     .inner_join(employees_teams::table.on(employees::id.eq(employees_teams::employee_id)))
+//!     .inner_join(teams::table.on(teams::id.eq(employees_teams::team_id)))
+//!     .select(TeamEmployee::as_select())
+//!     .load(conn)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Diesel only lets a column be selected from sources it knows about, so
+//! every table used with `as_of` needs an [`as_of_table!`](crate::as_of_table)
+//! line listing its columns. The demo tables in [`crate::schema`] have theirs
+//! next to their models.
+
+use std::fmt;
+
+use diesel::{
+    expression::ValidGrouping,
+    internal::table_macro::{FromClause, SelectStatement},
+    mysql::Mysql,
+    query_builder::{AsQuery, AstPass, QueryFragment, QueryId},
+    query_source::{QuerySource, TableNotEqual},
+    sql_types::{Text, Timestamp},
+    QueryResult, SelectableExpression, Table,
+};
+
+/// A commit hash, branch, tag or any other revision expression Dolt
+/// understands, e.g. `HEAD~2`.
+///
+/// This is what the procedures and table functions take. Only `AS OF` also
+/// accepts a point in time; see [`AsOfRevision`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Revision(String);
+
+impl Revision {
+    /// The revision as a string argument for procedures and table functions.
+    pub fn to_arg(&self) -> String {
+        self.0.clone()
+    }
+}
+
+impl From<&str> for Revision {
+    fn from(revision: &str) -> Self {
+        Revision(revision.to_string())
+    }
+}
+
+impl From<String> for Revision {
+    fn from(revision: String) -> Self {
+        Revision(revision)
+    }
+}
+
+impl fmt::Display for Revision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Anything Dolt accepts after `AS OF`: a [`Revision`] or a point in time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AsOfRevision {
+    Revision(Revision),
+    Timestamp(chrono::NaiveDateTime),
+}
+
+impl From<Revision> for AsOfRevision {
+    fn from(revision: Revision) -> Self {
+        AsOfRevision::Revision(revision)
+    }
+}
+
+impl From<&str> for AsOfRevision {
+    fn from(revision: &str) -> Self {
+        AsOfRevision::Revision(revision.into())
+    }
+}
+
+impl From<String> for AsOfRevision {
+    fn from(revision: String) -> Self {
+        AsOfRevision::Revision(revision.into())
+    }
+}
+
+impl From<chrono::NaiveDateTime> for AsOfRevision {
+    fn from(timestamp: chrono::NaiveDateTime) -> Self {
+        AsOfRevision::Timestamp(timestamp)
+    }
+}
+
+impl fmt::Display for AsOfRevision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AsOfRevision::Revision(r) => r.fmt(f),
+            AsOfRevision::Timestamp(t) => t.format("%Y-%m-%d %H:%M:%S%.f").fmt(f),
+        }
+    }
+}
+
+impl QueryFragment<Mysql> for AsOfRevision {
+    fn walk_ast<'b>(&'b self, mut pass: AstPass<'_, 'b, Mysql>) -> QueryResult<()> {
+        match self {
+            AsOfRevision::Revision(r) => pass.push_bind_param::<Text, _>(&r.0),
+            AsOfRevision::Timestamp(t) => pass.push_bind_param::<Timestamp, _>(t),
+        }
+    }
+}
+
+/// A table read `AS OF` an [`AsOfRevision`].
+#[derive(Debug, Clone)]
+pub struct AsOf<S> {
+    source: S,
+    revision: AsOfRevision,
+}
+
+impl<S> AsOf<S> {
+    /// The revision this table is read at.
+    pub fn revision(&self) -> &AsOfRevision {
+        &self.revision
+    }
+}
+
+/// Adds [`as_of`](AsOfDsl::as_of) to every Diesel table.
+pub trait AsOfDsl: Table + Sized {
+    /// Reads this table as it was at `revision`.
+    // Named after the SQL it renders, not a conversion.
+    #[allow(clippy::wrong_self_convention)]
+    fn as_of<R: Into<AsOfRevision>>(self, revision: R) -> AsOf<Self> {
+        AsOf {
+            source: self,
+            revision: revision.into(),
+        }
+    }
+}
+
+impl<T: Table> AsOfDsl for T {}
+
+impl<S> QueryId for AsOf<S> {
+    // The revision is a bind parameter of varying type, so don't cache the statement.
+    type QueryId = ();
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl<S> QuerySource for AsOf<S>
+where
+    S: Table + Clone,
+    <S as QuerySource>::DefaultSelection: ValidGrouping<()> + SelectableExpression<AsOf<S>>,
+{
+    type FromClause = Self;
+    type DefaultSelection = <S as QuerySource>::DefaultSelection;
+
+    fn from_clause(&self) -> Self::FromClause {
+        self.clone()
+    }
+
+    fn default_selection(&self) -> Self::DefaultSelection {
+        self.source.default_selection()
+    }
+}
+
+impl<S> QueryFragment<Mysql> for AsOf<S>
+where
+    S: QueryFragment<Mysql>,
+{
+    fn walk_ast<'b>(&'b self, mut pass: AstPass<'_, 'b, Mysql>) -> QueryResult<()> {
+        self.source.walk_ast(pass.reborrow())?;
+        pass.push_sql(" AS OF ");
+        self.revision.walk_ast(pass.reborrow())
+    }
+}
+
+impl<S> AsQuery for AsOf<S>
+where
+    S: Table + Clone,
+    <S as QuerySource>::DefaultSelection: ValidGrouping<()> + SelectableExpression<AsOf<S>>,
+{
+    type SqlType = <<Self as QuerySource>::DefaultSelection as diesel::Expression>::SqlType;
+    type Query = SelectStatement<FromClause<Self>>;
+
+    fn as_query(self) -> Self::Query {
+        SelectStatement::simple(self)
+    }
+}
+
+impl<S> Table for AsOf<S>
+where
+    S: Table + Clone + AsQuery,
+    <S as Table>::PrimaryKey: SelectableExpression<AsOf<S>>,
+    <S as Table>::AllColumns: SelectableExpression<AsOf<S>>,
+    <S as QuerySource>::DefaultSelection: ValidGrouping<()> + SelectableExpression<AsOf<S>>,
+{
+    type PrimaryKey = <S as Table>::PrimaryKey;
+    type AllColumns = <S as Table>::AllColumns;
+
+    fn primary_key(&self) -> Self::PrimaryKey {
+        self.source.primary_key()
+    }
+
+    fn all_columns() -> Self::AllColumns {
+        S::all_columns()
+    }
+}
+
+// Whatever may share a query with the table may share it with the table AS OF a revision.
+impl<S, T> TableNotEqual<T> for AsOf<S>
+where
+    S: TableNotEqual<T>,
+    T: Table,
+    AsOf<S>: Table,
+{
+}
+
+/// Lets the columns of a `table!` be selected from the table read `AS OF` a revision.
+///
+/// Like `diesel::joinable!`, the table module has to be in scope. Joins need
+/// an explicit `.on(...)` clause when one side is read `AS OF` a revision.
+///
+/// ```ignore
+/// use my_app::schema::employees;
+///
+/// dolt_dieselrs_getting_started::as_of_table!(employees { id, last_name, first_name });
+/// ```
+#[macro_export]
+macro_rules! as_of_table {
+    ($table:ident { $($column:ident),* $(,)? }) => {
+        impl ::diesel::query_source::AppearsInFromClause<$table::table>
+            for $crate::as_of::AsOf<$table::table>
+        {
+            type Count = ::diesel::query_source::Once;
+        }
+
+        $(
+            impl ::diesel::SelectableExpression<$crate::as_of::AsOf<$table::table>>
+                for $table::$column
+            {
+            }
+        )*
+    };
+}
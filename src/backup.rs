@@ -0,0 +1,92 @@
+//! Backups of a whole database through `DOLT_BACKUP`.
+//!
+//! Unlike a remote, a backup holds every branch, tag and the working sets,
+//! so a database restored from one picks up where the original left off.
+//! Like remotes, `file://` backups live on the sql-server's filesystem.
+
+use diesel::{ExpressionMethods, MysqlConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+
+use crate::{
+    custom_schema::dolt_backups,
+    error::DoltError,
+    models::{DoltBackup, DoltCallResponse},
+    procedure::DoltProcedure,
+};
+
+/// Lists all backups.
+pub fn backups(conn: &mut MysqlConnection) -> Result<Vec<DoltBackup>, DoltError> {
+    Ok(dolt_backups::table
+        .select(DoltBackup::as_select())
+        .order(dolt_backups::name.asc())
+        .load::<DoltBackup>(conn)?)
+}
+
+/// Whether a backup named `backup` exists.
+pub fn backup_exists(conn: &mut MysqlConnection, backup: &str) -> Result<bool, DoltError> {
+    let results = dolt_backups::table
+        .select(dolt_backups::name)
+        .filter(dolt_backups::name.eq(backup))
+        .load::<String>(conn)?;
+
+    Ok(!results.is_empty())
+}
+
+/// Adds a backup named `backup` writing to `url`.
+pub fn add_backup(conn: &mut MysqlConnection, backup: &str, url: &str) -> Result<(), DoltError> {
+    if backup_exists(conn, backup)? {
+        return Err(DoltError::BackupExists(backup.to_string()));
+    }
+
+    DoltProcedure::new("DOLT_BACKUP")
+        .args(["add", backup, url])
+        .subject(backup)
+        .load::<DoltCallResponse>(conn)?;
+
+    Ok(())
+}
+
+/// Removes the backup named `backup`, leaving the files it wrote in place.
+pub fn remove_backup(conn: &mut MysqlConnection, backup: &str) -> Result<(), DoltError> {
+    if !backup_exists(conn, backup)? {
+        return Err(DoltError::BackupNotFound(backup.to_string()));
+    }
+
+    DoltProcedure::new("DOLT_BACKUP")
+        .args(["remove", backup])
+        .load::<DoltCallResponse>(conn)?;
+
+    Ok(())
+}
+
+/// Copies the current state of the database to `backup`.
+pub fn sync_backup(conn: &mut MysqlConnection, backup: &str) -> Result<(), DoltError> {
+    if !backup_exists(conn, backup)? {
+        return Err(DoltError::BackupNotFound(backup.to_string()));
+    }
+
+    DoltProcedure::new("DOLT_BACKUP")
+        .args(["sync", backup])
+        .load::<DoltCallResponse>(conn)?;
+
+    Ok(())
+}
+
+/// Restores the backup at `url` as `database`, replacing an existing
+/// database of that name only when `force` is set.
+pub fn restore_backup(
+    conn: &mut MysqlConnection,
+    url: &str,
+    database: &str,
+    force: bool,
+) -> Result<(), DoltError> {
+    let mut procedure = DoltProcedure::new("DOLT_BACKUP").arg("restore");
+    if force {
+        procedure = procedure.arg("--force");
+    }
+
+    procedure
+        .args([url, database])
+        .load::<DoltCallResponse>(conn)?;
+
+    Ok(())
+}
@@ -0,0 +1,1413 @@
+use std::{collections::HashMap, env, process};
+
+use diesel::{
+    result::Error, sql_query, Connection, ExpressionMethods, JoinOnDsl, MysqlConnection,
+    NullableExpressionMethods, QueryDsl, RunQueryDsl, SelectableHelper,
+};
+use dotenv::dotenv;
+
+use dolt_dieselrs_getting_started::{
+    as_of::{AsOfDsl, AsOfRevision},
+    blame::ColumnValues,
+    column_diff::ColumnDiffFilter,
+    conflicts::{MergeReport, ViolationGroup},
+    connection::with_database,
+    log::LogQuery,
+    print::{print_as_table, print_records},
+    procedure::quote_identifier,
+    rebase::RebaseAction,
+    remote::file_remote_url,
+    schema::{
+        employees::dsl as employees, employees_teams::dsl as employees_teams, teams::dsl as teams,
+    },
+    DoltError, DoltRepo,
+};
+
+use dolt_dieselrs_getting_started::models::{
+    DoltBlameEntry, DoltDiffEmployeesEntry, DoltDiffEmployeesEntryAfterEdit,
+    DoltDiffEmployeesTeamsEntry, Employee, EmployeeAfterEdit, EmployeeTeam, EmployeeTeamInsertable,
+    Table, Team, TeamConflict, TeamEmployee, TeamEmployeeAfterEdit,
+};
+
+const START_TAG: &str = "demo-start";
+const LOG_PAGE_SIZE: u64 = 20;
+// The sql-server resolves this path, so it has to run on the same machine.
+const REMOTE_DIR: &str = "/tmp/dieselrs_big_demo_remote";
+const CLONE_DATABASE: &str = "dieselrs_big_demo_clone";
+const STAGING_DATABASE: &str = "dieselrs_big_demo_staging";
+// Unlike remotes and backups, patch files are written and read by this process.
+const PATCH_FILE: &str = "/tmp/dieselrs_big_demo.sql";
+const RESET_BACKUP: &str = "before-reset";
+const RESET_BACKUP_DIR: &str = "/tmp/dieselrs_big_demo_backup";
+
+const USAGE: &str = "\
+Usage:
+    dolt-dieselrs-getting-started [--backup-before-reset]
+    dolt-dieselrs-getting-started backup list
+    dolt-dieselrs-getting-started backup add <name> <url-or-absolute-path>
+    dolt-dieselrs-getting-started backup remove <name>
+    dolt-dieselrs-getting-started backup sync <name>
+    dolt-dieselrs-getting-started backup restore <url-or-absolute-path> <database> [--force]
+    dolt-dieselrs-getting-started blame <employees|teams|employees_teams>
+    dolt-dieselrs-getting-started column-history <table> <column>";
+
+fn main() -> Result<(), DoltError> {
+    dotenv().ok();
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut repo = DoltRepo::from_env()?;
+
+    let backup_before_reset = match args.first().map(String::as_str) {
+        Some("backup") => return run_backup_command(&mut repo, &args[1..]),
+        Some("blame") => return run_blame_command(&mut repo, &args[1..]),
+        Some("column-history") => match &args[1..] {
+            [table, column] => return print_column_history(&mut repo, table, column),
+            _ => usage(),
+        },
+        Some("--backup-before-reset") if args.len() == 1 => true,
+        None => false,
+        Some(_) => usage(),
+    };
+
+    repo.checkout("main")?;
+    print_active_branch(&mut repo)?;
+
+    // Start fresh so we can re-run this script.
+    if backup_before_reset {
+        dolt_backup_before_reset(&mut repo)?;
+    }
+    reset_database(&mut repo)?;
+    delete_non_main_branches(&mut repo)?;
+    delete_demo_tags(&mut repo)?;
+    delete_demo_remotes(&mut repo)?;
+    drop_demo_databases(&mut repo)?;
+
+    // Build our tables
+    setup_database(repo.connection())?;
+    print_tables(repo.connection())?;
+
+    // Our first Dolt feature. This will commit the first time
+    // But after that nothing has changed so there is nothing to commit.
+    match dolt_commit(&mut repo, "Tim <tim@dolthub.com>", "Created tables") {
+        Err(DoltError::NothingToCommit) => println!("Nothing to commit"),
+        result => result?,
+    }
+
+    // Examine a Dolt system table: dolt_log
+    print_commit_log(&mut repo, false)?;
+
+    // Load rows into the tables
+    insert_data(repo.connection())?;
+
+    print_summary_table(repo.connection(), false)?;
+
+    // Show off dolt_status and dolt_diff
+    print_status(&mut repo)?;
+    print_diff(&mut repo, "employees", false)?;
+
+    // Dolt commit our changes
+    dolt_commit(
+        &mut repo,
+        "Aaron <aaron@dolthub.com>",
+        "Inserted data into tables",
+    )?;
+
+    print_commit_log(&mut repo, false)?;
+
+    // Show off dolt_reset
+    drop_table(repo.connection(), "employees_teams")?;
+    print_status(&mut repo)?;
+    print_tables(repo.connection())?;
+    println!("Resetting to HEAD");
+    repo.reset_hard(None)?;
+    print_status(&mut repo)?;
+    print_tables(repo.connection())?;
+
+    // Show off branch and merge
+    dolt_create_branch(&mut repo, "modify_data")?;
+    repo.checkout("modify_data")?;
+    modify_data(repo.connection())?;
+    print_status(&mut repo)?;
+    print_diff(&mut repo, "employees", false)?;
+    print_diff(&mut repo, "employees_teams", false)?;
+    print_diff_stat(&mut repo, "HEAD", "WORKING")?;
+    print_summary_table(repo.connection(), false)?;
+    dolt_commit(
+        &mut repo,
+        "Brian <brian@dolthub.com>",
+        "Modified data on branch",
+    )?;
+    print_commit_log(&mut repo, false)?;
+
+    // Switch back to main because I want the same merge base
+    repo.checkout("main")?;
+    dolt_create_branch(&mut repo, "modify_schema")?;
+    repo.checkout("modify_schema")?;
+    print_active_branch(&mut repo)?;
+    modify_schema(repo.connection())?;
+    print_status(&mut repo)?;
+    print_diff(&mut repo, "employees", true)?;
+    print_diff_stat(&mut repo, "HEAD", "WORKING")?;
+    print_summary_table(repo.connection(), true)?;
+    dolt_commit(
+        &mut repo,
+        "Tim <tim@dolthub.com>",
+        "Modified schema on branch",
+    )?;
+    print_schema_diff(&mut repo, "main", "modify_schema")?;
+    print_commit_log(&mut repo, false)?;
+
+    // Show off merge
+    repo.checkout("main")?;
+    print_active_branch(&mut repo)?;
+    print_commit_log(&mut repo, false)?;
+    print_summary_table(repo.connection(), false)?;
+    print_branch_diff(&mut repo, "main", "modify_data", "employees")?;
+    dolt_merge(&mut repo, "modify_data")?;
+    print_summary_table(repo.connection(), false)?;
+    print_commit_log(&mut repo, false)?;
+    dolt_merge(&mut repo, "modify_schema")?;
+    println!("Verifying constraints");
+    match repo.verify_constraints(true)?.as_slice() {
+        [] => println!("\tNo violations"),
+        violations => print_violations(violations),
+    }
+    print_commit_log(&mut repo, false)?;
+    print_summary_table(repo.connection(), true)?;
+
+    // Show off log queries
+    print_log_query(
+        &mut repo,
+        "Commits touching employees_teams",
+        LogQuery::new().touching_table("employees_teams"),
+    )?;
+    print_log_query(
+        &mut repo,
+        "Commits by Tim on main, first parent only",
+        LogQuery::new().author("Tim").first_parent(),
+    )?;
+
+    // Show off column diffs: what changed per commit, and one column over time
+    print_column_changes(&mut repo)?;
+    print_column_history(&mut repo, "employees", "first_name")?;
+
+    // Show off tags
+    repo.create_tag("v1", "HEAD", Some("Merged modify_data and modify_schema"))?;
+    print_tags(&mut repo)?;
+
+    // Show off reverting a merge, on its own branch so main keeps start_date
+    dolt_revert_merge(&mut repo, "revert_modify_schema")?;
+
+    // Show off time travel
+    print_summary_table_as_of(repo.connection(), "modify_data")?;
+    print_employee_history(&mut repo, 0)?;
+
+    // Show off blame: who last changed Brian Fitzgerald's start_date?
+    print_column_blame(&mut repo, 3)?;
+
+    // Show off conflict resolution
+    dolt_create_branch(&mut repo, "rename_team")?;
+    repo.checkout("rename_team")?;
+    rename_team(repo.connection(), 1, "Revenue")?;
+    dolt_commit(
+        &mut repo,
+        "Aaron <aaron@dolthub.com>",
+        "Renamed Sales to Revenue",
+    )?;
+    repo.checkout("main")?;
+    rename_team(repo.connection(), 1, "Business Development")?;
+    dolt_commit(
+        &mut repo,
+        "Tim <tim@dolthub.com>",
+        "Renamed Sales to Business Development",
+    )?;
+    dolt_merge_resolving_conflicts(&mut repo, "rename_team")?;
+    print_commit_log(&mut repo, true)?;
+
+    // Show off interactive rebase: squash add_team into a single commit
+    dolt_create_branch(&mut repo, "add_team")?;
+    repo.checkout("add_team")?;
+    add_team(repo.connection(), 2, "Marketing")?;
+    dolt_commit(&mut repo, "Brian <brian@dolthub.com>", "Added Marketing")?;
+    rename_team(repo.connection(), 2, "Marketing & Communications")?;
+    dolt_commit(&mut repo, "Brian <brian@dolthub.com>", "Renamed Marketing")?;
+    dolt_squash(&mut repo, "main")?;
+    print_commit_log(&mut repo, false)?;
+    print_ahead_behind(&mut repo, "main")?;
+
+    // Show off cherry-pick and revert
+    repo.checkout("main")?;
+    dolt_cherry_pick(&mut repo, "add_team")?;
+    dolt_revert(&mut repo, "HEAD")?;
+    print_commit_log(&mut repo, false)?;
+
+    // Show off stash: carry uncommitted changes over to another branch
+    dolt_create_branch(&mut repo, "rename_engineering")?;
+    rename_team(repo.connection(), 0, "Research & Development")?;
+    print_status(&mut repo)?;
+    repo.checkout_with_autostash("rename_engineering")?;
+    print_active_branch(&mut repo)?;
+    print_status(&mut repo)?;
+    dolt_commit(
+        &mut repo,
+        "Aliyss <aliyss@dolthub.com>",
+        "Renamed Engineering",
+    )?;
+    repo.checkout("main")?;
+
+    // Show off remotes: push to a file:// remote, clone it and pull
+    let mut clone = dolt_push_and_clone(&mut repo)?;
+    add_team(repo.connection(), 3, "Support")?;
+    dolt_commit(&mut repo, "Tim <tim@dolthub.com>", "Added Support")?;
+    repo.push("origin", "main", false)?;
+    dolt_pull(&mut clone, "origin", "main")?;
+    print_commit_log(&mut clone, false)?;
+
+    // Show off patches: replay main's history on a staging database without a remote
+    dolt_patch_to_staging(&mut repo)?;
+
+    Ok(())
+}
+
+fn usage() -> ! {
+    eprintln!("{USAGE}");
+    process::exit(2)
+}
+
+/// Accepts backup locations as URLs or as absolute paths on the sql-server.
+fn backup_url(location: &str) -> String {
+    if location.contains("://") {
+        return location.to_string();
+    }
+
+    file_remote_url(location).unwrap_or_else(|| usage())
+}
+
+pub fn run_backup_command(repo: &mut DoltRepo, args: &[String]) -> Result<(), DoltError> {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args.as_slice() {
+        ["list"] => print_backups(repo)?,
+        ["add", name, location] => {
+            let url = backup_url(location);
+            repo.add_backup(name, &url)?;
+            println!("Added backup: {name} ({url})");
+        }
+        ["remove", name] => {
+            repo.remove_backup(name)?;
+            println!("Removed backup: {name}");
+        }
+        ["sync", name] => {
+            repo.sync_backup(name)?;
+            println!("Synced backup: {name}");
+        }
+        ["restore", location, database] | ["restore", location, database, "--force"] => {
+            let force = args.len() == 4;
+            repo.restore_backup(&backup_url(location), database, force)?;
+            println!("Restored {location} as: {database}");
+        }
+        _ => usage(),
+    }
+
+    Ok(())
+}
+
+pub fn run_blame_command(repo: &mut DoltRepo, args: &[String]) -> Result<(), DoltError> {
+    let table = match args {
+        [table] => table.as_str(),
+        _ => usage(),
+    };
+    let blame = repo.blame::<DoltBlameEntry>(table)?;
+
+    println!("Blame for {table}");
+    match table {
+        // The CLI looks at the database the demo leaves behind, with start_date.
+        "employees" => print_blame(
+            employees::employees
+                .select(EmployeeAfterEdit::as_select())
+                .load(repo.connection())?,
+            |employee| employee.id,
+            blame,
+        ),
+        "teams" => print_blame(
+            teams::teams
+                .select((teams::id, teams::name.assume_not_null()))
+                .load::<Team>(repo.connection())?,
+            |team| team.id,
+            blame,
+        ),
+        "employees_teams" => print_blame(
+            employees_teams::employees_teams.load::<EmployeeTeam>(repo.connection())?,
+            |employee_team| employee_team.id,
+            blame,
+        ),
+        _ => usage(),
+    }
+
+    Ok(())
+}
+
+pub fn print_blame<M: ColumnValues>(
+    rows: Vec<M>,
+    id: impl Fn(&M) -> i32,
+    blame: Vec<DoltBlameEntry>,
+) {
+    let Some(first) = rows.first() else {
+        println!("\tNo rows");
+        return;
+    };
+
+    let mut headers: Vec<String> = first
+        .column_values()
+        .into_iter()
+        .map(|(column, _)| column.to_string())
+        .collect();
+    headers.extend(["commit", "committer", "message"].map(String::from));
+
+    let records = rows
+        .iter()
+        .map(|row| {
+            let mut record: Vec<String> = row
+                .column_values()
+                .into_iter()
+                .map(|(_, value)| value)
+                .collect();
+            match blame.iter().find(|entry| entry.id == id(row)) {
+                Some(entry) => record.extend([
+                    entry.commit.clone(),
+                    entry.committer.clone(),
+                    entry.message.clone(),
+                ]),
+                None => record.extend(["_", "_", "_"].map(String::from)),
+            }
+            record
+        })
+        .collect();
+
+    print_records(headers, records);
+}
+
+/// Creates the directory `dir` if needed and returns its `file://` URL.
+fn local_directory_url(dir: &str) -> Result<String, DoltError> {
+    let url = file_remote_url(dir)
+        .ok_or_else(|| DoltError::InvalidArgument(format!("{dir} is not an absolute path")))?;
+    std::fs::create_dir_all(dir)?;
+
+    Ok(url)
+}
+
+pub fn dolt_backup_before_reset(repo: &mut DoltRepo) -> Result<(), DoltError> {
+    if !repo
+        .backups()?
+        .iter()
+        .any(|backup| backup.name == RESET_BACKUP)
+    {
+        let url = local_directory_url(RESET_BACKUP_DIR)?;
+        repo.add_backup(RESET_BACKUP, &url)?;
+        println!("Added backup: {RESET_BACKUP} ({url})");
+    }
+
+    repo.sync_backup(RESET_BACKUP)?;
+    println!("Backed up {} to: {RESET_BACKUP}", repo.database());
+    println!("\tRestore with: backup restore {RESET_BACKUP_DIR} <database>");
+
+    Ok(())
+}
+
+pub fn reset_database(repo: &mut DoltRepo) -> Result<(), DoltError> {
+    /*
+    The first run tags the root commit as demo-start,
+    every run after that resets straight to the tag.
+    */
+
+    if !repo.tag_exists(START_TAG)? {
+        // The root is the last commit in topological order, whatever the dates say.
+        let head = repo.hash_of("HEAD")?;
+        let graph = repo.commit_graph()?;
+
+        match graph.log(&head).last() {
+            Some(root) => {
+                let commit_hash = &root.commit.commit_hash;
+                println!("Tagging commit {commit_hash} as {START_TAG}");
+                repo.create_tag(START_TAG, commit_hash.as_str(), None)?;
+            }
+            None => {
+                println!("Resetting to HEAD");
+                return repo.reset_hard(None);
+            }
+        }
+    }
+
+    println!("Resetting to tag: {START_TAG}");
+    repo.reset_hard(Some(START_TAG))
+}
+
+pub fn delete_demo_tags(repo: &mut DoltRepo) -> Result<(), DoltError> {
+    for tag in repo.tags()? {
+        if tag.tag_name == START_TAG {
+            continue;
+        }
+
+        println!("Deleting tag: {}", tag.tag_name);
+        repo.delete_tag(&tag.tag_name)?;
+    }
+
+    Ok(())
+}
+
+pub fn delete_demo_remotes(repo: &mut DoltRepo) -> Result<(), DoltError> {
+    for remote in repo.remotes()? {
+        println!("Removing remote: {}", remote.name);
+        repo.remove_remote(&remote.name)?;
+    }
+
+    Ok(())
+}
+
+pub fn drop_demo_databases(repo: &mut DoltRepo) -> Result<(), DoltError> {
+    for database in [CLONE_DATABASE, STAGING_DATABASE] {
+        println!("Dropping database: {database}");
+        sql_query(format!(
+            "DROP DATABASE IF EXISTS {}",
+            quote_identifier(database)
+        ))
+        .execute(repo.connection())?;
+    }
+
+    Ok(())
+}
+
+pub fn delete_non_main_branches(repo: &mut DoltRepo) -> Result<(), DoltError> {
+    /*
+    Iterate through the non-main branches and delete them with
+    CALL DOLT_BRANCH('-D', '<branch>'). '-D' force deletes just in
+    case I have some unmerged modifications from a failed run.
+    */
+
+    for branch in repo.branches()? {
+        if branch.name == "main" {
+            continue;
+        }
+
+        println!("Deleting branch: {}", branch.name);
+        repo.delete_branch(&branch.name, true)?;
+    }
+
+    Ok(())
+}
+
+pub fn setup_database(conn: &mut MysqlConnection) -> Result<(), DoltError> {
+    /*
+    CREATE is not standard for diesel.rs
+    Usually Tables are not created dynamically with diesel.rs
+    diesel.rs takes pride in being correct at compile time.
+
+    This is only to stay more or less true to the original python demo.
+    */
+
+    let stmt_employees = "CREATE TABLE employees(
+       id INTEGER PRIMARY KEY,
+       last_name VARCHAR(255),
+       first_name VARCHAR(255)
+    )"
+    .to_string();
+
+    let stmt_teams = "CREATE TABLE teams(
+       id INTEGER PRIMARY KEY,
+       name VARCHAR(255)
+    )"
+    .to_string();
+
+    // diesel.rs dislikes tables without a primary key, so adding an auto_incrementing one.
+    let stmt_employees_teams = "CREATE TABLE employees_teams(
+       id INTEGER PRIMARY KEY AUTO_INCREMENT,
+       employee_id INTEGER NOT NULL, 
+       team_id INTEGER NOT NULL, 
+       FOREIGN KEY (employee_id) REFERENCES employees(id),
+       FOREIGN KEY (team_id) REFERENCES teams(id)
+    )"
+    .to_string();
+
+    sql_query(stmt_employees).execute(conn)?;
+    sql_query(stmt_teams).execute(conn)?;
+    sql_query(stmt_employees_teams).execute(conn)?;
+
+    Ok(())
+}
+
+pub fn insert_data(conn: &mut MysqlConnection) -> Result<(), DoltError> {
+    conn.transaction::<_, Error, _>(|conn| {
+        let employee_values: Vec<Employee> = vec![
+            Employee {
+                id: 0,
+                first_name: "Tim".to_string(),
+                last_name: "Sehn".to_string(),
+            },
+            Employee {
+                id: 1,
+                first_name: "Brian".to_string(),
+                last_name: "Hendricks".to_string(),
+            },
+            Employee {
+                id: 2,
+                first_name: "Aaron".to_string(),
+                last_name: "Son".to_string(),
+            },
+            Employee {
+                id: 3,
+                first_name: "Brian".to_string(),
+                last_name: "Fitzgerald".to_string(),
+            },
+            Employee {
+                id: 5,
+                first_name: "Aliyss".to_string(),
+                last_name: "Snow".to_string(),
+            },
+        ];
+
+        diesel::insert_into(employees::employees)
+            .values(&employee_values)
+            .execute(conn)?;
+
+        Ok(())
+    })?;
+
+    conn.transaction::<_, Error, _>(|conn| {
+        let team_values: Vec<Team> = vec![
+            Team {
+                id: 0,
+                name: "Engineering".to_string(),
+            },
+            Team {
+                id: 1,
+                name: "Sales".to_string(),
+            },
+        ];
+
+        diesel::insert_into(teams::teams)
+            .values(&team_values)
+            .execute(conn)?;
+
+        Ok(())
+    })?;
+
+    conn.transaction::<_, Error, _>(|conn| {
+        let employee_team_values: Vec<EmployeeTeamInsertable> = vec![
+            EmployeeTeamInsertable {
+                employee_id: 0,
+                team_id: 0,
+            },
+            EmployeeTeamInsertable {
+                employee_id: 1,
+                team_id: 0,
+            },
+            EmployeeTeamInsertable {
+                employee_id: 2,
+                team_id: 0,
+            },
+            EmployeeTeamInsertable {
+                employee_id: 0,
+                team_id: 1,
+            },
+            EmployeeTeamInsertable {
+                employee_id: 3,
+                team_id: 1,
+            },
+            EmployeeTeamInsertable {
+                employee_id: 5,
+                team_id: 0,
+            },
+        ];
+
+        diesel::insert_into(employees_teams::employees_teams)
+            .values(&employee_team_values)
+            .execute(conn)?;
+
+        Ok(())
+    })?;
+
+    Ok(())
+}
+
+pub fn modify_data(conn: &mut MysqlConnection) -> Result<(), DoltError> {
+    conn.transaction::<_, Error, _>(|conn| {
+        diesel::update(employees::employees.filter(employees::first_name.eq("Tim")))
+            .set(employees::first_name.eq("Timothy"))
+            .execute(conn)?;
+
+        Ok(())
+    })?;
+
+    conn.transaction::<_, Error, _>(|conn| {
+        diesel::insert_into(employees::employees)
+            .values(Employee {
+                id: 4,
+                first_name: "Daylon".to_string(),
+                last_name: "Wilkins".to_string(),
+            })
+            .execute(conn)?;
+
+        Ok(())
+    })?;
+
+    conn.transaction::<_, Error, _>(|conn| {
+        diesel::insert_into(employees_teams::employees_teams)
+            .values(EmployeeTeamInsertable {
+                employee_id: 4,
+                team_id: 0,
+            })
+            .execute(conn)?;
+
+        Ok(())
+    })?;
+
+    conn.transaction::<_, Error, _>(|conn| {
+        diesel::delete(
+            employees_teams::employees_teams
+                .filter(employees_teams::employee_id.eq(0))
+                .filter(employees_teams::team_id.eq(1)),
+        )
+        .execute(conn)?;
+
+        Ok(())
+    })?;
+
+    Ok(())
+}
+
+pub fn modify_schema(conn: &mut MysqlConnection) -> Result<(), DoltError> {
+    /*
+    ALTER is not standard for diesel.rs
+
+    This is only to stay more or less true to the original python demo.
+    */
+
+    let stmt = "ALTER TABLE employees add column start_date date".to_string();
+    conn.transaction::<_, Error, _>(|conn| {
+        sql_query(stmt).execute(conn)?;
+        Ok(())
+    })?;
+
+    conn.transaction::<_, Error, _>(|conn| {
+        diesel::update(employees::employees.filter(employees::id.eq(0)))
+            .set(
+                employees::start_date.eq(chrono::NaiveDate::from_ymd_opt(2018, 8, 6)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap()),
+            )
+            .execute(conn)?;
+
+        diesel::update(employees::employees.filter(employees::id.eq(1)))
+            .set(
+                employees::start_date.eq(chrono::NaiveDate::from_ymd_opt(2018, 8, 6)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap()),
+            )
+            .execute(conn)?;
+
+        diesel::update(employees::employees.filter(employees::id.eq(2)))
+            .set(
+                employees::start_date.eq(chrono::NaiveDate::from_ymd_opt(2018, 8, 6)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap()),
+            )
+            .execute(conn)?;
+
+        diesel::update(employees::employees.filter(employees::id.eq(5)))
+            .set(
+                employees::start_date.eq(chrono::NaiveDate::from_ymd_opt(2023, 7, 4)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap()),
+            )
+            .execute(conn)?;
+
+        let target_employees = employees::employees
+            .filter(employees::last_name.eq("Fitzgerald"))
+            .limit(1)
+            .load::<EmployeeAfterEdit>(conn)?;
+
+        if let Some(target_employee) = target_employees.first() {
+            diesel::update(employees::employees.filter(employees::id.eq(target_employee.id)))
+                .set(
+                    employees::start_date.eq(chrono::NaiveDate::from_ymd_opt(2021, 4, 19)
+                        .unwrap()
+                        .and_hms_opt(0, 0, 0)
+                        .unwrap()),
+                )
+                .execute(conn)?;
+        }
+
+        Ok(())
+    })?;
+
+    Ok(())
+}
+
+pub fn add_team(conn: &mut MysqlConnection, id: i32, name: &str) -> Result<(), DoltError> {
+    diesel::insert_into(teams::teams)
+        .values(Team {
+            id,
+            name: name.to_string(),
+        })
+        .execute(conn)?;
+
+    Ok(())
+}
+
+pub fn rename_team(conn: &mut MysqlConnection, id: i32, name: &str) -> Result<(), DoltError> {
+    diesel::update(teams::teams.filter(teams::id.eq(id)))
+        .set(teams::name.eq(name))
+        .execute(conn)?;
+
+    Ok(())
+}
+
+pub fn drop_table(conn: &mut MysqlConnection, table: &str) -> Result<(), DoltError> {
+    /*
+    DROP is not standard for diesel.rs
+
+    This is only to stay more or less true to the original python demo.
+    */
+
+    let table_name = match table {
+        "employees" | "teams" | "employees_teams" => table,
+        _ => return Err(DoltError::UnknownTable(table.to_string())),
+    };
+
+    let stmt = format!("DROP TABLE {table_name}");
+    sql_query(stmt).execute(conn)?;
+
+    Ok(())
+}
+
+pub fn dolt_commit(repo: &mut DoltRepo, author: &str, message: &str) -> Result<(), DoltError> {
+    let hash = repo.commit(author, message)?;
+    println!("Created commit: {hash}");
+
+    Ok(())
+}
+
+pub fn dolt_create_branch(repo: &mut DoltRepo, branch: &str) -> Result<(), DoltError> {
+    repo.create_branch(branch)?;
+    println!("Created branch: {branch}");
+
+    Ok(())
+}
+
+pub fn dolt_merge(repo: &mut DoltRepo, branch: &str) -> Result<(), DoltError> {
+    let outcome = repo.merge(branch)?;
+
+    println!("Merge Complete: {branch}");
+    println!("\tCommit: {}", &outcome.merge.hash);
+    println!("\tFast Forward: {}", &outcome.merge.fast_forward);
+    println!("\tConflicts: {}", &outcome.merge.conflicts);
+    print_merge_report(&outcome.report);
+
+    Ok(())
+}
+
+pub fn dolt_push_and_clone(repo: &mut DoltRepo) -> Result<DoltRepo, DoltError> {
+    /*
+    The remote directory survives between runs with the history of the
+    previous one, which the reset main has diverged from. Force the push
+    so the remote starts over too.
+    */
+
+    let url = local_directory_url(REMOTE_DIR)?;
+
+    repo.add_remote("origin", &url)?;
+    println!("Added remote: origin ({url})");
+    repo.push("origin", "main", true)?;
+    println!("Pushed main to origin");
+
+    let clone = repo.clone_database(&url, CLONE_DATABASE)?;
+    println!("Cloned origin into: {CLONE_DATABASE}");
+
+    Ok(clone)
+}
+
+pub fn dolt_patch_to_staging(repo: &mut DoltRepo) -> Result<(), DoltError> {
+    let count = repo.write_patch_file(START_TAG, "main", PATCH_FILE)?;
+    println!("Wrote {count} statements from {START_TAG}..main to: {PATCH_FILE}");
+
+    sql_query(format!(
+        "CREATE DATABASE {}",
+        quote_identifier(STAGING_DATABASE)
+    ))
+    .execute(repo.connection())?;
+    let mut staging = DoltRepo::connect(&with_database(repo.database_url(), STAGING_DATABASE)?)?;
+
+    let hash = staging.apply_patch_file(
+        PATCH_FILE,
+        "Tim <tim@dolthub.com>",
+        &format!("Applied {START_TAG}..main from {}", repo.database()),
+    )?;
+    println!("Applied {PATCH_FILE} to {STAGING_DATABASE}: {hash}");
+    print_summary_table(staging.connection(), true)?;
+
+    Ok(())
+}
+
+pub fn dolt_pull(repo: &mut DoltRepo, remote: &str, branch: &str) -> Result<(), DoltError> {
+    let outcome = repo.pull(remote, Some(branch))?;
+
+    println!("Pull Complete: {remote}/{branch} into {}", repo.database());
+    println!("\tFast Forward: {}", &outcome.pull.fast_forward);
+    println!("\tConflicts: {}", &outcome.pull.conflicts);
+    print_merge_report(&outcome.report);
+
+    Ok(())
+}
+
+pub fn print_merge_report(report: &MergeReport) {
+    if report.is_clean() {
+        return;
+    }
+
+    for table in &report.conflicts {
+        println!(
+            "\tConflicts in {}: {}",
+            table.table_name, table.num_conflicts
+        );
+    }
+
+    for conflict in &report.schema_conflicts {
+        println!(
+            "\tSchema conflict in {}: {}",
+            conflict.table_name, conflict.description
+        );
+    }
+
+    print_violations(&report.violations);
+}
+
+pub fn print_violations(violations: &[ViolationGroup]) {
+    for group in violations {
+        println!(
+            "\t{} violations in {}: {}",
+            group.violation_type,
+            group.table_name,
+            group.violations.len()
+        );
+    }
+}
+
+pub fn dolt_squash(repo: &mut DoltRepo, upstream: &str) -> Result<(), DoltError> {
+    println!("Rebasing {} onto {upstream}", repo.branch());
+
+    let mut plan = repo.start_rebase(upstream)?;
+    for step in plan.iter_mut().skip(1) {
+        step.action = RebaseAction::Fixup;
+        repo.update_rebase_step(step)?;
+    }
+
+    for step in &plan {
+        println!(
+            "\t{} {}: {}",
+            step.action, step.commit_hash, step.commit_message
+        );
+    }
+
+    repo.continue_rebase()
+}
+
+pub fn dolt_revert_merge(repo: &mut DoltRepo, branch: &str) -> Result<(), DoltError> {
+    dolt_create_branch(repo, branch)?;
+    repo.checkout(branch)?;
+
+    println!("Reverting merge HEAD to its first parent on: {branch}");
+    let hash = repo.revert_merge(
+        "HEAD",
+        1,
+        "Tim <tim@dolthub.com>",
+        "Reverted the merge of modify_schema",
+    )?;
+    println!("\tCommit: {hash}");
+    print_summary_table(repo.connection(), false)?;
+
+    repo.checkout("main")
+}
+
+pub fn dolt_revert(repo: &mut DoltRepo, revision: &str) -> Result<(), DoltError> {
+    let outcome = repo.revert(revision, "Tim <tim@dolthub.com>")?;
+
+    println!("Reverted: {revision}");
+    println!("\tCommit: {}", outcome.hash.as_deref().unwrap_or_default());
+
+    if !outcome.report.is_clean() {
+        print_merge_report(&outcome.report);
+        repo.abort_revert()?;
+    }
+
+    Ok(())
+}
+
+pub fn dolt_cherry_pick(repo: &mut DoltRepo, revision: &str) -> Result<(), DoltError> {
+    let outcome = repo.cherry_pick(revision)?;
+
+    println!("Cherry-picked: {revision}");
+    println!(
+        "\tCommit: {}",
+        outcome.pick.hash.as_deref().unwrap_or_default()
+    );
+    println!("\tConflicts: {}", outcome.pick.data_conflicts);
+    println!("\tSchema Conflicts: {}", outcome.pick.schema_conflicts);
+    println!(
+        "\tConstraint Violations: {}",
+        outcome.pick.constraint_violations
+    );
+
+    if !outcome.report.is_clean() {
+        print_merge_report(&outcome.report);
+        repo.abort_cherry_pick()?;
+    }
+
+    Ok(())
+}
+
+pub fn dolt_merge_resolving_conflicts(repo: &mut DoltRepo, branch: &str) -> Result<(), DoltError> {
+    let outcome = repo.merge_keeping_conflicts(branch)?;
+    println!(
+        "Merge of {branch} left {} conflicts",
+        outcome.merge.conflicts
+    );
+    print_merge_report(&outcome.report);
+    print_as_table(repo.table_conflicts::<TeamConflict>()?);
+
+    // Keep both names rather than picking a side.
+    let resolved = repo.resolve_conflicts_with::<TeamConflict, _>(|conn, conflict| {
+        let name = format!(
+            "{} & {}",
+            conflict.our_name.as_deref().unwrap_or_default(),
+            conflict.their_name.as_deref().unwrap_or_default()
+        );
+
+        diesel::update(teams::teams.filter(teams::id.nullable().eq(conflict.our_id)))
+            .set(teams::name.eq(name))
+            .execute(conn)?;
+
+        Ok(())
+    })?;
+    println!("Resolved {resolved} conflicts");
+
+    let hash = repo.commit_merge("Tim <tim@dolthub.com>", &format!("Merged {branch}"))?;
+    println!("Created merge commit: {hash}");
+
+    Ok(())
+}
+
+pub fn print_backups(repo: &mut DoltRepo) -> Result<(), DoltError> {
+    println!("Backups:");
+
+    for backup in repo.backups()? {
+        println!("\t{}: {}", backup.name, backup.url);
+    }
+
+    Ok(())
+}
+
+pub fn print_tags(repo: &mut DoltRepo) -> Result<(), DoltError> {
+    println!("Tags:");
+
+    for tag in repo.tags()? {
+        println!("\t{}: {} ({})", tag.tag_name, tag.tag_hash, tag.message);
+    }
+
+    Ok(())
+}
+
+pub fn print_commit_log(repo: &mut DoltRepo, graph: bool) -> Result<(), DoltError> {
+    // Examine the commit log through the DOLT_LOG() table function
+
+    println!("Commit Log:");
+
+    if graph {
+        return print_commit_graph(repo);
+    }
+
+    // Page through the log rather than loading it all at once.
+    let mut page = LogQuery::new()
+        .limit(LOG_PAGE_SIZE)
+        .load(repo.connection())?;
+    while let Some(last) = page.last() {
+        for commit in &page {
+            println!(
+                "\t{}: {} by {}",
+                commit.commit_hash, commit.message, commit.committer
+            );
+        }
+
+        page = LogQuery::new()
+            .after(last)
+            .limit(LOG_PAGE_SIZE)
+            .load(repo.connection())?;
+    }
+
+    Ok(())
+}
+
+pub fn print_log_query(repo: &mut DoltRepo, title: &str, query: LogQuery) -> Result<(), DoltError> {
+    println!("{title}:");
+
+    for commit in query.load(repo.connection())? {
+        println!(
+            "\t{}: {} by {}",
+            commit.commit_hash, commit.message, commit.committer
+        );
+    }
+
+    Ok(())
+}
+
+pub fn print_commit_graph(repo: &mut DoltRepo) -> Result<(), DoltError> {
+    /*
+    dolt_log flattens merges, so draw the branch from dolt_commits and
+    dolt_commit_ancestors instead, naming the branches at each commit
+    like `git log --graph --decorate`.
+    */
+
+    let mut heads: HashMap<String, Vec<String>> = HashMap::new();
+    for branch in repo.branches()? {
+        heads.entry(branch.hash).or_default().push(branch.name);
+    }
+
+    let head = repo.hash_of(repo.branch().to_string())?;
+    let graph = repo.commit_graph()?;
+    let lines = graph.render(&head, |node| {
+        let commit = &node.commit;
+        let decoration = match heads.get(&commit.commit_hash) {
+            Some(branches) => format!(" ({})", branches.join(", ")),
+            None => String::new(),
+        };
+
+        format!(
+            "{}{decoration}: {} by {}",
+            &commit.commit_hash[..8],
+            commit.message,
+            commit.committer
+        )
+    });
+
+    for line in lines {
+        println!("\t{line}");
+    }
+
+    Ok(())
+}
+
+pub fn print_ahead_behind(repo: &mut DoltRepo, upstream: &str) -> Result<(), DoltError> {
+    let (ahead, behind) = repo.ahead_behind(upstream)?;
+    let base = repo.merge_base(repo.branch().to_string(), upstream)?;
+
+    println!("{} compared to {upstream}:", repo.branch());
+    println!("\tAhead: {ahead}");
+    println!("\tBehind: {behind}");
+    println!("\tMerge Base: {base}");
+
+    Ok(())
+}
+
+pub fn print_status(repo: &mut DoltRepo) -> Result<(), DoltError> {
+    println!("Status");
+
+    let results = repo.status()?;
+
+    match results.len() {
+        0 => println!("\tNo tables modified"),
+        _ => {
+            for status in results {
+                println!("\t{}: {}", status.table_name, status.status);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn print_active_branch(repo: &mut DoltRepo) -> Result<(), DoltError> {
+    println!("Active Branch: {}", repo.active_branch()?);
+
+    Ok(())
+}
+
+pub fn print_diff(
+    repo: &mut DoltRepo,
+    table: &str,
+    with_start_date: bool,
+) -> Result<(), DoltError> {
+    println!("Diffing table: {table}");
+
+    match table {
+        "employees" => {
+            if !with_start_date {
+                print_as_table(repo.diff::<DoltDiffEmployeesEntry>(table)?);
+            } else {
+                print_as_table(repo.diff::<DoltDiffEmployeesEntryAfterEdit>(table)?);
+            }
+        }
+        "employees_teams" => {
+            print_as_table(repo.diff::<DoltDiffEmployeesTeamsEntry>(table)?);
+        }
+        _ => return Err(DoltError::UnknownTable(table.to_string())),
+    };
+
+    Ok(())
+}
+
+pub fn print_diff_stat(repo: &mut DoltRepo, from: &str, to: &str) -> Result<(), DoltError> {
+    /*
+    DOLT_DIFF_SUMMARY knows every changed table and whether its schema
+    changed, DOLT_DIFF_STAT has the counts. One line per table, like
+    `git diff --stat`.
+    */
+
+    println!("Diff stat: {from}..{to}");
+
+    let stats = repo.diff_stat(from, to, None)?;
+    let summaries = repo.diff_summary(from, to, None)?;
+    if summaries.is_empty() {
+        println!("\tNo changes");
+    }
+
+    for summary in summaries {
+        let table = if summary.to_table_name.is_empty() {
+            &summary.from_table_name
+        } else {
+            &summary.to_table_name
+        };
+
+        let mut line = format!("\t{table:<16} | {}", summary.diff_type);
+        if let Some(stat) = stats.iter().find(|stat| &stat.table_name == table) {
+            line.push_str(&format!(
+                ", rows +{} -{} ~{}, cells +{} -{} ~{}",
+                stat.rows_added,
+                stat.rows_deleted,
+                stat.rows_modified,
+                stat.cells_added,
+                stat.cells_deleted,
+                stat.cells_modified
+            ));
+        }
+        if summary.schema_change {
+            line.push_str(", schema changed");
+        }
+
+        println!("{line}");
+    }
+
+    Ok(())
+}
+
+pub fn print_schema_diff(repo: &mut DoltRepo, from: &str, to: &str) -> Result<(), DoltError> {
+    println!("Schema diff: {from}..{to}");
+
+    for table in repo.schema_diff(from, to, None)? {
+        for change in table.changes() {
+            println!("\t{change}");
+        }
+
+        for line in table.unified_diff(3).lines() {
+            println!("\t{line}");
+        }
+    }
+
+    Ok(())
+}
+
+pub fn print_column_changes(repo: &mut DoltRepo) -> Result<(), DoltError> {
+    println!("Columns changed per commit");
+
+    for changes in repo.column_changes(&ColumnDiffFilter::all())? {
+        let columns: Vec<String> = changes
+            .columns
+            .iter()
+            .map(|(column, diff_type)| format!("{column} ({diff_type})"))
+            .collect();
+
+        println!(
+            "\t{}: {}: {}",
+            changes.commit_hash,
+            changes.table_name,
+            columns.join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+pub fn print_column_history(
+    repo: &mut DoltRepo,
+    table: &str,
+    column: &str,
+) -> Result<(), DoltError> {
+    println!("Changes to {table}.{column} over time");
+
+    let filter = ColumnDiffFilter::table(table).column(column);
+    for change in repo.column_history(&filter)? {
+        println!(
+            "\t{} {}: {} by {} ({})",
+            change.date, change.commit_hash, change.message, change.committer, change.diff_type
+        );
+    }
+
+    Ok(())
+}
+
+pub fn print_employee_history(repo: &mut DoltRepo, id: i32) -> Result<(), DoltError> {
+    println!("History of employee {id}");
+
+    for version in repo.history_of::<Employee>(id)? {
+        println!(
+            "\t{}: {} {} by {}",
+            version.commit_hash, version.row.first_name, version.row.last_name, version.committer
+        );
+    }
+
+    Ok(())
+}
+
+pub fn print_column_blame(repo: &mut DoltRepo, id: i32) -> Result<(), DoltError> {
+    println!("Blame for employee {id}");
+
+    for blame in repo.column_blame::<EmployeeAfterEdit>(id)? {
+        println!(
+            "\t{} = {}: {} by {}",
+            blame.column, blame.value, blame.commit_hash, blame.committer
+        );
+    }
+
+    Ok(())
+}
+
+pub fn print_branch_diff(
+    repo: &mut DoltRepo,
+    from: &str,
+    to: &str,
+    table: &str,
+) -> Result<(), DoltError> {
+    println!("Diffing table {table}: {from}...{to}");
+
+    match table {
+        "employees" => {
+            print_as_table(repo.diff_from_merge_base::<DoltDiffEmployeesEntry>(from, to, table)?)
+        }
+        "employees_teams" => print_as_table(
+            repo.diff_from_merge_base::<DoltDiffEmployeesTeamsEntry>(from, to, table)?,
+        ),
+        _ => return Err(DoltError::UnknownTable(table.to_string())),
+    };
+
+    Ok(())
+}
+
+pub fn print_tables(conn: &mut MysqlConnection) -> Result<(), DoltError> {
+    // Raw SQL here to show what we've done
+
+    let stmt = "SHOW tables".to_string();
+    let result = sql_query(stmt).load::<Table>(conn)?;
+
+    println!("Tables in database: ");
+
+    for table in result {
+        println!("\t{}", table.name);
+    }
+
+    Ok(())
+}
+
+pub fn print_summary_table(
+    conn: &mut MysqlConnection,
+    with_start_date: bool,
+) -> Result<(), DoltError> {
+    println!("Team Summary");
+
+    // Dolt supports up to 12 table joins. Here we do a 3 table join.
+    let query_build = employees::employees
+        .inner_join(
+            employees_teams::employees_teams.on(employees::id.eq(employees_teams::employee_id)),
+        )
+        .inner_join(teams::teams.on(teams::id.eq(employees_teams::team_id)))
+        .order(teams::name.asc());
+
+    match with_start_date {
+        true => {
+            let result = query_build
+                .select(TeamEmployeeAfterEdit::as_select())
+                .load::<TeamEmployeeAfterEdit>(conn)?;
+
+            for employee_team in result {
+                let mut output = format!(
+                    "\t{}: {} {}",
+                    employee_team.team_name.unwrap_or("".to_string()),
+                    employee_team.first_name.unwrap_or("".to_string()),
+                    employee_team.last_name.unwrap_or("".to_string())
+                );
+
+                if let Some(start_date) = employee_team.start_date {
+                    output += &format!(" {}", start_date.format("%Y-%m-%d"))
+                }
+
+                println!("{output}");
+            }
+        }
+        false => {
+            let result = query_build
+                .select(TeamEmployee::as_select())
+                .load::<TeamEmployee>(conn)?;
+
+            for employee_team in result {
+                let output = format!(
+                    "\t{}: {} {}",
+                    employee_team.team_name.unwrap_or("".to_string()),
+                    employee_team.first_name.unwrap_or("".to_string()),
+                    employee_team.last_name.unwrap_or("".to_string())
+                );
+
+                println!("{output}");
+            }
+        }
+    };
+
+    Ok(())
+}
+
+pub fn print_summary_table_as_of<R: Into<AsOfRevision>>(
+    conn: &mut MysqlConnection,
+    revision: R,
+) -> Result<(), DoltError> {
+    let revision = revision.into();
+    println!("Team Summary as of {revision}");
+
+    // The same 3 table join, but every table is read as it was at the revision.
+    let result = employees::employees
+        .as_of(revision.clone())
+        .inner_join(
+            employees_teams::employees_teams
+                .as_of(revision.clone())
+                .on(employees::id.eq(employees_teams::employee_id)),
+        )
+        .inner_join(
+            teams::teams
+                .as_of(revision)
+                .on(teams::id.eq(employees_teams::team_id)),
+        )
+        .order(teams::name.asc())
+        .select(TeamEmployee::as_select())
+        .load::<TeamEmployee>(conn)?;
+
+    for employee_team in result {
+        let output = format!(
+            "\t{}: {} {}",
+            employee_team.team_name.unwrap_or("".to_string()),
+            employee_team.first_name.unwrap_or("".to_string()),
+            employee_team.last_name.unwrap_or("".to_string())
+        );
+
+        println!("{output}");
+    }
+
+    Ok(())
+}
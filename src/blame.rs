@@ -0,0 +1,128 @@
+//! Who last changed a row, or a single field of it.
+//!
+//! `dolt_blame_<table>` names the commit that last touched each row. For a
+//! single field, [`column_blame`] walks the row's versions from
+//! `dolt_history_<table>` and remembers where each value first appeared:
+//!
+//! ```no_run
+//! # use dolt_dieselrs_getting_started::{models::EmployeeAfterEdit, DoltError, DoltRepo};
+//! # fn run(repo: &mut DoltRepo) -> Result<(), DoltError> {
+//! for blame in repo.column_blame::<EmployeeAfterEdit>(3)? {
+//!     println!("{} = {}: {} by {}", blame.column, blame.value, blame.commit_hash, blame.committer);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use diesel::{mysql::Mysql, sql_query, MysqlConnection, QueryableByName, RunQueryDsl};
+
+use crate::{
+    error::DoltError,
+    history::{history_of, DoltHistory},
+    models::{display_option, Employee, EmployeeAfterEdit, EmployeeTeam, Team},
+    procedure::quote_identifier,
+};
+
+/// The rows of `dolt_blame_<table>`, loaded into `T`, such as
+/// [`DoltBlameEntry`](crate::models::DoltBlameEntry).
+pub fn blame<T>(conn: &mut MysqlConnection, table: &str) -> Result<Vec<T>, DoltError>
+where
+    T: QueryableByName<Mysql> + 'static,
+{
+    let stmt = format!(
+        "SELECT * FROM {}",
+        quote_identifier(&format!("dolt_blame_{table}"))
+    );
+
+    sql_query(stmt)
+        .load::<T>(conn)
+        .map_err(|e| DoltError::from_table_query(table, e))
+}
+
+/// Models that can list their fields by column, for [`column_blame`].
+pub trait ColumnValues {
+    /// Each column name with the field's value, `_` for `None`.
+    fn column_values(&self) -> Vec<(&'static str, String)>;
+}
+
+/// The commit a field got its current value in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnBlame {
+    pub column: &'static str,
+    pub value: String,
+    pub commit_hash: String,
+    pub committer: String,
+    pub commit_date: chrono::NaiveDateTime,
+}
+
+/// For each column of the row with the given id, the commit that last
+/// changed it, in column order.
+///
+/// Only commits reachable from HEAD count. A value that was changed and
+/// later changed back is blamed on the commit that changed it back.
+pub fn column_blame<M>(conn: &mut MysqlConnection, id: M::Id) -> Result<Vec<ColumnBlame>, DoltError>
+where
+    M: DoltHistory + ColumnValues,
+{
+    let mut blame: Vec<ColumnBlame> = Vec::new();
+
+    for version in history_of::<M>(conn, id)? {
+        for (index, (column, value)) in version.row.column_values().into_iter().enumerate() {
+            match blame.get_mut(index) {
+                Some(previous) if previous.value == value => {}
+                Some(previous) => {
+                    previous.value = value;
+                    previous.commit_hash = version.commit_hash.clone();
+                    previous.committer = version.committer.clone();
+                    previous.commit_date = version.commit_date;
+                }
+                None => blame.push(ColumnBlame {
+                    column,
+                    value,
+                    commit_hash: version.commit_hash.clone(),
+                    committer: version.committer.clone(),
+                    commit_date: version.commit_date,
+                }),
+            }
+        }
+    }
+
+    Ok(blame)
+}
+
+impl ColumnValues for Employee {
+    fn column_values(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("id", self.id.to_string()),
+            ("last_name", self.last_name.clone()),
+            ("first_name", self.first_name.clone()),
+        ]
+    }
+}
+
+impl ColumnValues for EmployeeAfterEdit {
+    fn column_values(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("id", self.id.to_string()),
+            ("last_name", display_option(&self.last_name)),
+            ("first_name", display_option(&self.first_name)),
+            ("start_date", display_option(&self.start_date)),
+        ]
+    }
+}
+
+impl ColumnValues for Team {
+    fn column_values(&self) -> Vec<(&'static str, String)> {
+        vec![("id", self.id.to_string()), ("name", self.name.clone())]
+    }
+}
+
+impl ColumnValues for EmployeeTeam {
+    fn column_values(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("id", self.id.to_string()),
+            ("employee_id", self.employee_id.to_string()),
+            ("team_id", self.team_id.to_string()),
+        ]
+    }
+}
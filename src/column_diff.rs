@@ -0,0 +1,131 @@
+//! Which columns each commit changed, from the `dolt_column_diff` system table.
+//!
+//! Where a row diff shows whole rows, this only names the columns, which is
+//! enough to follow one column over time:
+//!
+//! ```no_run
+//! # use dolt_dieselrs_getting_started::{column_diff::ColumnDiffFilter, DoltError, DoltRepo};
+//! # fn run(repo: &mut DoltRepo) -> Result<(), DoltError> {
+//! let filter = ColumnDiffFilter::table("employees").column("first_name");
+//! for change in repo.column_history(&filter)? {
+//!     println!("{}: {} by {}", change.commit_hash, change.message, change.committer);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use diesel::{ExpressionMethods, MysqlConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+
+use crate::{custom_schema::dolt_column_diff, error::DoltError, models::DoltColumnDiff};
+
+/// Narrows column changes down to one table, and optionally one column.
+#[derive(Debug, Clone, Default)]
+pub struct ColumnDiffFilter {
+    table: Option<String>,
+    column: Option<String>,
+}
+
+impl ColumnDiffFilter {
+    /// Changes to every table.
+    pub fn all() -> Self {
+        ColumnDiffFilter::default()
+    }
+
+    /// Changes to `table`.
+    pub fn table(table: &str) -> Self {
+        ColumnDiffFilter {
+            table: Some(table.to_string()),
+            column: None,
+        }
+    }
+
+    /// Only changes to `column`.
+    pub fn column(mut self, column: &str) -> Self {
+        self.column = Some(column.to_string());
+        self
+    }
+}
+
+/// The columns of one table that one commit changed.
+#[derive(Debug, Clone)]
+pub struct ColumnChanges {
+    pub commit_hash: String,
+    pub committer: String,
+    pub email: String,
+    pub date: chrono::NaiveDateTime,
+    pub message: String,
+    pub table_name: String,
+    /// The changed columns with how they changed: `added`, `modified` or
+    /// `removed`.
+    pub columns: Vec<(String, String)>,
+}
+
+/// Every column change matching `filter`, newest first.
+pub fn column_diff(
+    conn: &mut MysqlConnection,
+    filter: &ColumnDiffFilter,
+) -> Result<Vec<DoltColumnDiff>, DoltError> {
+    let mut query = dolt_column_diff::table
+        .select(DoltColumnDiff::as_select())
+        .into_boxed();
+
+    if let Some(table) = &filter.table {
+        query = query.filter(dolt_column_diff::table_name.eq(table));
+    }
+    if let Some(column) = &filter.column {
+        query = query.filter(dolt_column_diff::column_name.eq(column));
+    }
+
+    query
+        .order((
+            dolt_column_diff::date.desc(),
+            dolt_column_diff::commit_hash.asc(),
+            dolt_column_diff::table_name.asc(),
+            dolt_column_diff::column_name.asc(),
+        ))
+        .load::<DoltColumnDiff>(conn)
+        .map_err(|e| DoltError::from_table_query("dolt_column_diff", e))
+}
+
+/// The column changes matching `filter`, grouped per commit and table,
+/// newest first.
+pub fn column_changes(
+    conn: &mut MysqlConnection,
+    filter: &ColumnDiffFilter,
+) -> Result<Vec<ColumnChanges>, DoltError> {
+    let mut changes: Vec<ColumnChanges> = Vec::new();
+
+    // Rows come ordered by commit and table, so each group is a single run.
+    for row in column_diff(conn, filter)? {
+        match changes.last_mut() {
+            Some(last)
+                if last.commit_hash == row.commit_hash && last.table_name == row.table_name =>
+            {
+                last.columns.push((row.column_name, row.diff_type));
+            }
+            _ => changes.push(ColumnChanges {
+                commit_hash: row.commit_hash,
+                committer: row.committer,
+                email: row.email,
+                date: row.date,
+                message: row.message,
+                table_name: row.table_name,
+                columns: vec![(row.column_name, row.diff_type)],
+            }),
+        }
+    }
+
+    Ok(changes)
+}
+
+/// The changes matching `filter` oldest first, for following a column over
+/// time.
+pub fn column_history(
+    conn: &mut MysqlConnection,
+    filter: &ColumnDiffFilter,
+) -> Result<Vec<DoltColumnDiff>, DoltError> {
+    let mut history = column_diff(conn, filter)?;
+    history.reverse();
+
+    Ok(history)
+}
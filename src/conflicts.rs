@@ -0,0 +1,307 @@
+//! Inspecting and resolving merge conflicts, schema conflicts and
+//! constraint violations.
+//!
+//! By default Dolt rolls a conflicting merge back. After
+//! [`DoltRepo::merge_keeping_conflicts`](crate::DoltRepo::merge_keeping_conflicts)
+//! the conflicts stay in the working set instead, one row per conflicting
+//! row in `dolt_conflicts_<table>`. Row types for those tables come from
+//! `#[derive(DoltConflict)]`. [`merge_report`] sums up everything a merge
+//! left behind, grouped by table.
+//!
+//! ```no_run
+//! # use diesel::prelude::*;
+//! # use dolt_dieselrs_getting_started::{models::TeamConflict, schema::teams, DoltError, DoltRepo};
+//! # fn run(repo: &mut DoltRepo) -> Result<(), DoltError> {
+//! repo.merge_keeping_conflicts("rename_team")?;
+//! repo.resolve_conflicts_with::<TeamConflict, _>(|conn, conflict| {
+//!     diesel::update(teams::table.filter(teams::id.nullable().eq(conflict.our_id)))
+//!         .set(teams::name.eq(&conflict.their_name))
+//!         .execute(conn)?;
+//!     Ok(())
+//! })?;
+//! repo.commit_merge("Tim <tim@dolthub.com>", "Merged rename_team")?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+
+use diesel::{
+    connection::{AnsiTransactionManager, SimpleConnection, TransactionManager},
+    mysql::Mysql,
+    sql_query,
+    sql_types::Text,
+    Connection, MysqlConnection, QueryDsl, QueryableByName, RunQueryDsl, SelectableHelper,
+};
+
+use crate::{
+    custom_schema::{dolt_conflicts, dolt_constraint_violations, dolt_schema_conflicts},
+    error::DoltError,
+    models::{
+        DoltCallResponse, DoltCherryPickResponse, DoltConstraintViolation, DoltMergeResponse,
+        DoltPullResponse, DoltSchemaConflict, DoltTableConflicts, DoltTableViolations,
+        DoltVerifyConstraintsResponse,
+    },
+    procedure::{quote_identifier, DoltProcedure},
+};
+
+/// A row of a `dolt_conflicts_<table>` system table.
+///
+/// Implemented by the row types `#[derive(DoltConflict)]` generates.
+pub trait ConflictRow: QueryableByName<Mysql> + 'static {
+    /// The table the conflicts are in.
+    const TABLE: &'static str;
+
+    /// The `dolt_conflict_id` that identifies this conflict.
+    fn conflict_id(&self) -> &str;
+}
+
+/// Which side of a merge wins when resolving a whole table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictSide {
+    /// Keep the rows of the branch that was merged into.
+    Ours,
+    /// Take the rows of the branch that was merged in.
+    Theirs,
+}
+
+impl ConflictSide {
+    fn flag(self) -> &'static str {
+        match self {
+            ConflictSide::Ours => "--ours",
+            ConflictSide::Theirs => "--theirs",
+        }
+    }
+}
+
+/// Constraint violations of one kind in one table.
+#[derive(Debug)]
+pub struct ViolationGroup {
+    pub table_name: String,
+    pub violation_type: String,
+    pub violations: Vec<DoltConstraintViolation>,
+}
+
+/// Everything a merge left to be dealt with before it can be committed.
+#[derive(Debug, Default)]
+pub struct MergeReport {
+    pub conflicts: Vec<DoltTableConflicts>,
+    pub schema_conflicts: Vec<DoltSchemaConflict>,
+    pub violations: Vec<ViolationGroup>,
+}
+
+impl MergeReport {
+    /// Whether there is nothing to resolve.
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty() && self.schema_conflicts.is_empty() && self.violations.is_empty()
+    }
+}
+
+/// The row `DOLT_MERGE` returned, plus what the merge left behind.
+#[derive(Debug)]
+pub struct MergeOutcome {
+    pub merge: DoltMergeResponse,
+    pub report: MergeReport,
+}
+
+/// The row `DOLT_CHERRY_PICK` returned, plus what the pick left behind.
+#[derive(Debug)]
+pub struct CherryPickOutcome {
+    pub pick: DoltCherryPickResponse,
+    pub report: MergeReport,
+}
+
+/// The commit `DOLT_REVERT` made, plus what the revert left behind.
+#[derive(Debug)]
+pub struct RevertOutcome {
+    /// The new commit, missing when the revert left something to resolve.
+    pub hash: Option<String>,
+    pub report: MergeReport,
+}
+
+/// The row `DOLT_PULL` returned, plus what the pull's merge left behind.
+#[derive(Debug)]
+pub struct PullOutcome {
+    pub pull: DoltPullResponse,
+    pub report: MergeReport,
+}
+
+/// Lets (or stops letting) the session commit while conflicts or constraint
+/// violations are unresolved.
+pub fn allow_commit_conflicts(conn: &mut MysqlConnection, allow: bool) -> Result<(), DoltError> {
+    // System variables can't be bound.
+    let flag = i32::from(allow);
+    let stmt = format!(
+        "SET @@dolt_allow_commit_conflicts = {flag}, @@dolt_force_transaction_commit = {flag}"
+    );
+    conn.batch_execute(&stmt)?;
+
+    Ok(())
+}
+
+/// Runs `f` with [`allow_commit_conflicts`] on. If `f` fails, it is turned
+/// back off before the error is returned, so a failed merge doesn't leave
+/// the session committing over conflicts.
+pub(crate) fn allowing_commit_conflicts<T>(
+    conn: &mut MysqlConnection,
+    f: impl FnOnce(&mut MysqlConnection) -> Result<T, DoltError>,
+) -> Result<T, DoltError> {
+    allow_commit_conflicts(conn, true)?;
+
+    f(conn).inspect_err(|_| {
+        // The original error says more than a failed reset would.
+        let _ = allow_commit_conflicts(conn, false);
+    })
+}
+
+/// Tables with unresolved conflicts and how many each has.
+pub fn conflicts(conn: &mut MysqlConnection) -> Result<Vec<DoltTableConflicts>, DoltError> {
+    Ok(dolt_conflicts::table
+        .select(DoltTableConflicts::as_select())
+        .load::<DoltTableConflicts>(conn)?)
+}
+
+/// Tables whose schemas conflict.
+pub fn schema_conflicts(conn: &mut MysqlConnection) -> Result<Vec<DoltSchemaConflict>, DoltError> {
+    Ok(dolt_schema_conflicts::table
+        .select(DoltSchemaConflict::as_select())
+        .load::<DoltSchemaConflict>(conn)?)
+}
+
+/// Constraint violations in the working set, grouped by table and violation type.
+pub fn constraint_violations(conn: &mut MysqlConnection) -> Result<Vec<ViolationGroup>, DoltError> {
+    let tables = dolt_constraint_violations::table
+        .select(DoltTableViolations::as_select())
+        .load::<DoltTableViolations>(conn)?;
+
+    let mut groups = Vec::new();
+    for table in tables {
+        // Only the columns every violations table has; the rest depends on the table.
+        let stmt = format!(
+            "SELECT violation_type, CAST(violation_info AS CHAR) AS violation_info FROM {}",
+            quote_identifier(&format!("dolt_constraint_violations_{}", table.table_name))
+        );
+        let violations = sql_query(stmt)
+            .load::<DoltConstraintViolation>(conn)
+            .map_err(|e| DoltError::from_table_query(&table.table_name, e))?;
+
+        let mut by_type: BTreeMap<String, Vec<DoltConstraintViolation>> = BTreeMap::new();
+        for violation in violations {
+            by_type
+                .entry(violation.violation_type.clone())
+                .or_default()
+                .push(violation);
+        }
+
+        groups.extend(
+            by_type
+                .into_iter()
+                .map(|(violation_type, violations)| ViolationGroup {
+                    table_name: table.table_name.clone(),
+                    violation_type,
+                    violations,
+                }),
+        );
+    }
+
+    Ok(groups)
+}
+
+/// Conflicts, schema conflicts and constraint violations in the working set.
+pub fn merge_report(conn: &mut MysqlConnection) -> Result<MergeReport, DoltError> {
+    Ok(MergeReport {
+        conflicts: conflicts(conn)?,
+        schema_conflicts: schema_conflicts(conn)?,
+        violations: constraint_violations(conn)?,
+    })
+}
+
+/// Checks the foreign keys, unique indexes and checks of the working set
+/// (or of every row with `all`) without changing anything.
+pub fn verify_constraints(
+    conn: &mut MysqlConnection,
+    all: bool,
+) -> Result<Vec<ViolationGroup>, DoltError> {
+    /*
+    DOLT_VERIFY_CONSTRAINTS writes what it finds to the violations tables.
+    Read them in a transaction and roll it back, so checking leaves the
+    working set as it was.
+    */
+
+    AnsiTransactionManager::begin_transaction(conn)?;
+
+    let result = (|| {
+        let mut procedure = DoltProcedure::new("DOLT_VERIFY_CONSTRAINTS");
+        if all {
+            procedure = procedure.arg("--all");
+        }
+
+        let response = procedure.load::<DoltVerifyConstraintsResponse>(conn)?;
+        match response.first() {
+            Some(response) if response.violations > 0 => constraint_violations(conn),
+            _ => Ok(Vec::new()),
+        }
+    })();
+
+    AnsiTransactionManager::rollback_transaction(conn)?;
+    result
+}
+
+/// Every unresolved conflict in the table of `C`.
+pub fn table_conflicts<C: ConflictRow>(conn: &mut MysqlConnection) -> Result<Vec<C>, DoltError> {
+    let stmt = format!(
+        "SELECT * FROM {}",
+        quote_identifier(&format!("dolt_conflicts_{}", C::TABLE))
+    );
+
+    sql_query(stmt)
+        .load::<C>(conn)
+        .map_err(|e| DoltError::from_table_query(C::TABLE, e))
+}
+
+/// Resolves every conflict in `table` in favour of one side.
+pub fn resolve_conflicts(
+    conn: &mut MysqlConnection,
+    table: &str,
+    side: ConflictSide,
+) -> Result<(), DoltError> {
+    DoltProcedure::new("DOLT_CONFLICTS_RESOLVE")
+        .args([side.flag(), table])
+        .load::<DoltCallResponse>(conn)?;
+
+    Ok(())
+}
+
+/// Resolves the conflicts in the table of `C` one row at a time.
+///
+/// `resolve` writes the merged row to the table; the conflict is cleared
+/// right after. Either every conflict is resolved or, when `resolve` fails,
+/// none is. Returns the number of resolved conflicts.
+pub fn resolve_conflicts_with<C, F>(
+    conn: &mut MysqlConnection,
+    mut resolve: F,
+) -> Result<usize, DoltError>
+where
+    C: ConflictRow,
+    F: FnMut(&mut MysqlConnection, &C) -> Result<(), DoltError>,
+{
+    let stmt = format!(
+        "DELETE FROM {} WHERE dolt_conflict_id = ?",
+        quote_identifier(&format!("dolt_conflicts_{}", C::TABLE))
+    );
+
+    conn.transaction::<_, DoltError, _>(|conn| {
+        let rows = table_conflicts::<C>(conn)?;
+
+        for row in &rows {
+            resolve(conn, row)?;
+
+            sql_query(&stmt)
+                .bind::<Text, _>(row.conflict_id())
+                .execute(conn)
+                .map_err(|e| DoltError::from_table_query(C::TABLE, e))?;
+        }
+
+        Ok(rows.len())
+    })
+}
@@ -0,0 +1,84 @@
+//! Connections pinned to a single branch.
+//!
+//! Dolt exposes every branch as its own database named `<database>/<branch>`.
+//! Diesel can't put that name in the connection URL (it refuses a second path
+//! segment and doesn't percent-decode `%2F`), so we connect to the plain
+//! database and `USE` the branch database right after.
+
+use diesel::{connection::SimpleConnection, Connection, MysqlConnection};
+
+use crate::{error::DoltError, procedure::quote_identifier, repo::active_branch};
+
+/// The database name in a `mysql://` URL.
+pub fn database_name(database_url: &str) -> Result<String, DoltError> {
+    let invalid = || {
+        DoltError::Connection(diesel::ConnectionError::InvalidConnectionUrl(format!(
+            "No database in {database_url}"
+        )))
+    };
+
+    let url = url::Url::parse(database_url).map_err(|_| invalid())?;
+    match url.path_segments().and_then(|mut segments| segments.next()) {
+        Some("") | None => Err(invalid()),
+        Some(database) => Ok(database.to_string()),
+    }
+}
+
+/// `database_url` with its database swapped for `database`.
+pub fn with_database(database_url: &str, database: &str) -> Result<String, DoltError> {
+    database_name(database_url)?;
+
+    let mut url = url::Url::parse(database_url).map_err(|_| {
+        DoltError::Connection(diesel::ConnectionError::InvalidConnectionUrl(
+            database_url.to_string(),
+        ))
+    })?;
+    url.set_path(database);
+
+    Ok(url.to_string())
+}
+
+/// Opens a new connection bound to `branch` of the database in `database_url`.
+pub fn establish_branch_connection(
+    database_url: &str,
+    branch: &str,
+) -> Result<MysqlConnection, DoltError> {
+    let database = database_name(database_url)?;
+    let mut conn = MysqlConnection::establish(database_url)?;
+    use_branch(&mut conn, &database, branch)?;
+
+    Ok(conn)
+}
+
+/// Binds an existing connection to `branch` and checks it landed there.
+pub fn use_branch(
+    conn: &mut MysqlConnection,
+    database: &str,
+    branch: &str,
+) -> Result<(), DoltError> {
+    // USE isn't allowed as a prepared statement, so this goes over the text protocol.
+    let stmt = format!("USE {}", quote_identifier(&format!("{database}/{branch}")));
+    conn.batch_execute(&stmt).map_err(|e| match &e {
+        diesel::result::Error::DatabaseError(_, info)
+            if info.message().contains("database not found") =>
+        {
+            DoltError::BranchNotFound(branch.to_string())
+        }
+        _ => DoltError::Query(e),
+    })?;
+
+    verify_branch(conn, branch)
+}
+
+/// Fails with [`DoltError::WrongBranch`] unless the session is on `branch`.
+pub fn verify_branch(conn: &mut MysqlConnection, branch: &str) -> Result<(), DoltError> {
+    let actual = active_branch(conn)?;
+    if actual != branch {
+        return Err(DoltError::WrongBranch {
+            expected: branch.to_string(),
+            actual,
+        });
+    }
+
+    Ok(())
+}
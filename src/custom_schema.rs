@@ -28,3 +28,139 @@ diesel::table! {
     status -> Text,
     }
 }
+
+diesel::table! {
+    dolt_history_employees (id, commit_hash) {
+    id -> Integer,
+    last_name -> Nullable<Text>,
+    first_name -> Nullable<Text>,
+    start_date -> Nullable<Timestamp>,
+    commit_hash -> Text,
+    committer -> Text,
+    commit_date -> Timestamp
+    }
+}
+
+diesel::table! {
+    dolt_history_teams (id, commit_hash) {
+    id -> Integer,
+    name -> Nullable<Text>,
+    commit_hash -> Text,
+    committer -> Text,
+    commit_date -> Timestamp
+    }
+}
+
+diesel::table! {
+    dolt_history_employees_teams (id, commit_hash) {
+    id -> Integer,
+    employee_id -> Integer,
+    team_id -> Integer,
+    commit_hash -> Text,
+    committer -> Text,
+    commit_date -> Timestamp
+    }
+}
+
+diesel::table! {
+    dolt_conflicts (table_name) {
+    #[sql_name = "table"]
+    table_name -> Text,
+    num_conflicts -> Unsigned<BigInt>,
+    }
+}
+
+diesel::table! {
+    dolt_schema_conflicts (table_name) {
+    table_name -> Text,
+    base_schema -> Text,
+    our_schema -> Text,
+    their_schema -> Text,
+    description -> Text,
+    }
+}
+
+diesel::table! {
+    dolt_constraint_violations (table_name) {
+    #[sql_name = "table"]
+    table_name -> Text,
+    num_violations -> Unsigned<BigInt>,
+    }
+}
+
+diesel::table! {
+    dolt_tags (tag_name) {
+    tag_name -> Text,
+    tag_hash -> Text,
+    tagger -> Text,
+    email -> Text,
+    date -> Timestamp,
+    message -> Text,
+    }
+}
+
+diesel::table! {
+    dolt_column_diff (commit_hash, table_name, column_name) {
+    commit_hash -> Text,
+    table_name -> Text,
+    column_name -> Text,
+    committer -> Text,
+    email -> Text,
+    date -> Timestamp,
+    message -> Text,
+    diff_type -> Text,
+    }
+}
+
+diesel::table! {
+    dolt_commits (commit_hash) {
+    commit_hash -> Text,
+    committer -> Text,
+    email -> Text,
+    date -> Timestamp,
+    message -> Text,
+    }
+}
+
+diesel::table! {
+    dolt_commit_ancestors (commit_hash, parent_index) {
+    commit_hash -> Text,
+    parent_hash -> Text,
+    parent_index -> Integer,
+    }
+}
+
+diesel::table! {
+    dolt_stashes (name, stash_id) {
+    name -> Text,
+    stash_id -> Text,
+    branch -> Text,
+    hash -> Text,
+    commit_message -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    dolt_remotes (name) {
+    name -> Text,
+    url -> Text,
+    }
+}
+
+diesel::table! {
+    dolt_backups (name) {
+    name -> Text,
+    url -> Text,
+    }
+}
+
+diesel::table! {
+    dolt_remote_branches (name) {
+    name -> Text,
+    hash -> Text,
+    latest_committer -> Text,
+    latest_committer_email -> Text,
+    latest_commit_date -> Timestamp,
+    latest_commit_message -> Text,
+    }
+}
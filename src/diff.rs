@@ -0,0 +1,168 @@
+//! Diffs between any two revisions.
+//!
+//! [`DoltRepo::diff`](crate::DoltRepo::diff) only shows the working set.
+//! These read the `DOLT_DIFF()` table function and the
+//! `dolt_commit_diff_<table>` system table instead, and load the same row
+//! types (see [`DoltDiff`](crate::DoltDiff)). For changes too big to read
+//! row by row, [`diff_stat`] and [`diff_summary`] count them per table.
+//!
+//! ```no_run
+//! # use dolt_dieselrs_getting_started::{diff::diff_from_merge_base, models::DoltDiffEmployeesEntry, DoltError};
+//! # fn run(conn: &mut diesel::MysqlConnection) -> Result<(), DoltError> {
+//! // What modify_data changed since it branched off main, like `git diff main...modify_data`.
+//! let rows = diff_from_merge_base::<DoltDiffEmployeesEntry, _, _>(conn, "main", "modify_data", "employees")?;
+//! # Ok(())
+//! # }
+//! ```
+
+use diesel::{
+    mysql::Mysql, sql_query, sql_types::Text, MysqlConnection, QueryableByName, RunQueryDsl,
+};
+
+use crate::{
+    as_of::Revision,
+    error::DoltError,
+    models::{DoltDiffStat, DoltDiffSummary},
+    procedure::quote_identifier,
+};
+
+/// Changes to `table` going from `from` to `to`, like `git diff from..to`.
+pub fn diff_between<T, F, R>(
+    conn: &mut MysqlConnection,
+    from: F,
+    to: R,
+    table: &str,
+) -> Result<Vec<T>, DoltError>
+where
+    T: QueryableByName<Mysql> + 'static,
+    F: Into<Revision>,
+    R: Into<Revision>,
+{
+    sql_query("SELECT * FROM DOLT_DIFF(?, ?, ?)")
+        .into_boxed::<Mysql>()
+        .bind::<Text, _>(from.into().to_arg())
+        .bind::<Text, _>(to.into().to_arg())
+        .bind::<Text, _>(table)
+        .load::<T>(conn)
+        .map_err(|e| DoltError::from_table_query(table, e))
+}
+
+/// Changes to `table` on `to` since it diverged from `from`, like `git diff from...to`.
+pub fn diff_from_merge_base<T, F, R>(
+    conn: &mut MysqlConnection,
+    from: F,
+    to: R,
+    table: &str,
+) -> Result<Vec<T>, DoltError>
+where
+    T: QueryableByName<Mysql> + 'static,
+    F: Into<Revision>,
+    R: Into<Revision>,
+{
+    // Dolt diffs against the merge base when given a single 'from...to' argument.
+    let range = format!("{}...{}", from.into().to_arg(), to.into().to_arg());
+
+    sql_query("SELECT * FROM DOLT_DIFF(?, ?)")
+        .into_boxed::<Mysql>()
+        .bind::<Text, _>(range)
+        .bind::<Text, _>(table)
+        .load::<T>(conn)
+        .map_err(|e| DoltError::from_table_query(table, e))
+}
+
+/// Changes to `table` between two commits, read from `dolt_commit_diff_<table>`.
+///
+/// Both commits are hashes, or `WORKING` / `STAGED` for uncommitted changes.
+pub fn commit_diff<T>(
+    conn: &mut MysqlConnection,
+    from_commit: &str,
+    to_commit: &str,
+    table: &str,
+) -> Result<Vec<T>, DoltError>
+where
+    T: QueryableByName<Mysql> + 'static,
+{
+    // dolt_commit_diff_<table> can only be read with both commits in the WHERE clause.
+    let stmt = format!(
+        "SELECT * FROM {} WHERE from_commit = ? AND to_commit = ?",
+        quote_identifier(&format!("dolt_commit_diff_{table}"))
+    );
+
+    sql_query(stmt)
+        .into_boxed::<Mysql>()
+        .bind::<Text, _>(from_commit)
+        .bind::<Text, _>(to_commit)
+        .load::<T>(conn)
+        .map_err(|e| DoltError::from_table_query(table, e))
+}
+
+/// Per-table row and cell counts of the changes going from `from` to `to`,
+/// for every changed table or just `table`.
+pub fn diff_stat(
+    conn: &mut MysqlConnection,
+    from: impl Into<Revision>,
+    to: impl Into<Revision>,
+    table: Option<&str>,
+) -> Result<Vec<DoltDiffStat>, DoltError> {
+    /*
+    The counts are NULL for tables whose rows can't be compared, such as
+    keyless tables or ones whose primary key changed. Report those as 0.
+    */
+
+    let counts = [
+        "rows_unmodified",
+        "rows_added",
+        "rows_deleted",
+        "rows_modified",
+        "cells_added",
+        "cells_deleted",
+        "cells_modified",
+        "old_row_count",
+        "new_row_count",
+        "old_cell_count",
+        "new_cell_count",
+    ]
+    .map(|column| format!("CAST(COALESCE({column}, 0) AS SIGNED) AS {column}"))
+    .join(", ");
+    let args = if table.is_some() { "?, ?, ?" } else { "?, ?" };
+    let stmt = format!("SELECT table_name, {counts} FROM DOLT_DIFF_STAT({args})");
+
+    let mut query = sql_query(stmt)
+        .into_boxed::<Mysql>()
+        .bind::<Text, _>(from.into().to_arg())
+        .bind::<Text, _>(to.into().to_arg());
+    if let Some(table) = table {
+        query = query.bind::<Text, _>(table);
+    }
+
+    query
+        .load::<DoltDiffStat>(conn)
+        .map_err(|e| DoltError::from_table_query(table.unwrap_or("DOLT_DIFF_STAT"), e))
+}
+
+/// Which tables changed going from `from` to `to`, and whether their data,
+/// schema or both did, for every changed table or just `table`.
+pub fn diff_summary(
+    conn: &mut MysqlConnection,
+    from: impl Into<Revision>,
+    to: impl Into<Revision>,
+    table: Option<&str>,
+) -> Result<Vec<DoltDiffSummary>, DoltError> {
+    let args = if table.is_some() { "?, ?, ?" } else { "?, ?" };
+    let stmt = format!(
+        "SELECT from_table_name, to_table_name, diff_type, data_change, schema_change \
+         FROM DOLT_DIFF_SUMMARY({args})"
+    );
+
+    let mut query = sql_query(stmt)
+        .into_boxed::<Mysql>()
+        .bind::<Text, _>(from.into().to_arg())
+        .bind::<Text, _>(to.into().to_arg());
+    if let Some(table) = table {
+        query = query.bind::<Text, _>(table);
+    }
+
+    query
+        .load::<DoltDiffSummary>(conn)
+        .map_err(|e| DoltError::from_table_query(table.unwrap_or("DOLT_DIFF_SUMMARY"), e))
+}
@@ -0,0 +1,198 @@
+use std::fmt;
+
+use diesel::result::{ConnectionError, Error};
+
+/// Everything that can go wrong talking to Dolt.
+#[derive(Debug)]
+pub enum DoltError {
+    /// Could not connect to the Dolt sql-server (or DATABASE_URL is missing).
+    Connection(ConnectionError),
+    /// A CALL DOLT_*() procedure failed for a reason we don't recognise.
+    Procedure { procedure: String, source: Error },
+    /// A plain query (system tables, DDL, DML) failed.
+    Query(Error),
+    /// DOLT_COMMIT found no staged changes.
+    NothingToCommit,
+    /// Merging the given branch (or picking, reverting or rebasing the given
+    /// commit) left conflicts behind.
+    MergeConflict(String),
+    /// The given table does not exist.
+    UnknownTable(String),
+    /// The given branch already exists.
+    BranchExists(String),
+    /// The given branch does not exist.
+    BranchNotFound(String),
+    /// The given tag already exists.
+    TagExists(String),
+    /// The given tag does not exist.
+    TagNotFound(String),
+    /// The given remote already exists.
+    RemoteExists(String),
+    /// The given remote does not exist.
+    RemoteNotFound(String),
+    /// The given commit, branch or tag does not resolve to a commit.
+    RevisionNotFound(String),
+    /// A database with the given name already exists.
+    DatabaseExists(String),
+    /// The given backup already exists.
+    BackupExists(String),
+    /// The given backup does not exist.
+    BackupNotFound(String),
+    /// An argument the operation can't work with, and why.
+    InvalidArgument(String),
+    /// The working set has uncommitted changes to the given tables.
+    UncommittedChanges(String),
+    /// Reading or writing a patch file failed.
+    Io(std::io::Error),
+    /// A connection ended up on a different branch than the one requested.
+    WrongBranch { expected: String, actual: String },
+    /// No pooled connection became available in time.
+    #[cfg(feature = "r2d2")]
+    Pool(diesel::r2d2::PoolError),
+}
+
+impl DoltError {
+    /// Maps a failed `CALL <procedure>()` onto a variant, `subject` being the
+    /// branch, tag, remote, backup, database or commit the call acted on.
+    /// Whether something exists, is missing or conflicts, the procedure
+    /// decides what kind of thing it is.
+    pub fn from_procedure(procedure: &str, subject: &str, error: Error) -> Self {
+        /*
+        Dolt reports every procedure failure as a generic MySQL error,
+        so the only thing to go on is the message text.
+        */
+
+        let message = match &error {
+            Error::DatabaseError(_, info) => info.message().to_lowercase(),
+            _ => {
+                return DoltError::Procedure {
+                    procedure: procedure.to_string(),
+                    source: error,
+                }
+            }
+        };
+
+        let already_exists = message.contains("already exists");
+        let not_found = message.contains("not found")
+            || message.contains("could not find")
+            || message.contains("not a valid");
+
+        if message.contains("nothing to commit") {
+            DoltError::NothingToCommit
+        } else if message.contains("conflict")
+            && matches!(
+                procedure,
+                // The procedures that merge, so a conflict is a merge conflict.
+                "DOLT_MERGE" | "DOLT_PULL" | "DOLT_CHERRY_PICK" | "DOLT_REVERT" | "DOLT_REBASE"
+            )
+        {
+            DoltError::MergeConflict(subject.to_string())
+        } else if message.contains("table not found") {
+            DoltError::UnknownTable(subject.to_string())
+        } else if already_exists {
+            match procedure {
+                "DOLT_BRANCH" | "DOLT_CHECKOUT" => DoltError::BranchExists(subject.to_string()),
+                "DOLT_TAG" => DoltError::TagExists(subject.to_string()),
+                "DOLT_REMOTE" => DoltError::RemoteExists(subject.to_string()),
+                // A restore collides with a database, adding with another backup.
+                "DOLT_BACKUP" if !message.contains("database") => {
+                    DoltError::BackupExists(subject.to_string())
+                }
+                "DOLT_BACKUP" | "DOLT_CLONE" => DoltError::DatabaseExists(subject.to_string()),
+                _ => DoltError::Procedure {
+                    procedure: procedure.to_string(),
+                    source: error,
+                },
+            }
+        } else if not_found {
+            match procedure {
+                "DOLT_BRANCH" | "DOLT_CHECKOUT" | "DOLT_MERGE" | "DOLT_FETCH" | "DOLT_PULL"
+                | "DOLT_PUSH" => DoltError::BranchNotFound(subject.to_string()),
+                "DOLT_TAG" => DoltError::TagNotFound(subject.to_string()),
+                "DOLT_REMOTE" => DoltError::RemoteNotFound(subject.to_string()),
+                "DOLT_BACKUP" => DoltError::BackupNotFound(subject.to_string()),
+                _ => DoltError::RevisionNotFound(subject.to_string()),
+            }
+        } else {
+            DoltError::Procedure {
+                procedure: procedure.to_string(),
+                source: error,
+            }
+        }
+    }
+
+    /// Maps a failed query against `table` (or one of its system tables).
+    pub fn from_table_query(table: &str, error: Error) -> Self {
+        match &error {
+            Error::DatabaseError(_, info) if info.message().contains("table not found") => {
+                DoltError::UnknownTable(table.to_string())
+            }
+            _ => DoltError::Query(error),
+        }
+    }
+}
+
+impl fmt::Display for DoltError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DoltError::Connection(e) => write!(f, "Error connecting to Dolt: {e}"),
+            DoltError::Procedure { procedure, source } => {
+                write!(f, "Error executing procedure {procedure}: {source}")
+            }
+            DoltError::Query(e) => write!(f, "Error executing query: {e}"),
+            DoltError::NothingToCommit => write!(f, "Nothing to commit"),
+            DoltError::MergeConflict(branch) => write!(f, "Merge conflict: {branch}"),
+            DoltError::UnknownTable(table) => write!(f, "Unknown table: {table}"),
+            DoltError::BranchExists(branch) => write!(f, "Branch exists: {branch}"),
+            DoltError::BranchNotFound(branch) => write!(f, "Branch not found: {branch}"),
+            DoltError::TagExists(tag) => write!(f, "Tag exists: {tag}"),
+            DoltError::TagNotFound(tag) => write!(f, "Tag not found: {tag}"),
+            DoltError::RemoteExists(remote) => write!(f, "Remote exists: {remote}"),
+            DoltError::RemoteNotFound(remote) => write!(f, "Remote not found: {remote}"),
+            DoltError::RevisionNotFound(revision) => write!(f, "Revision not found: {revision}"),
+            DoltError::DatabaseExists(database) => write!(f, "Database exists: {database}"),
+            DoltError::BackupExists(backup) => write!(f, "Backup exists: {backup}"),
+            DoltError::BackupNotFound(backup) => write!(f, "Backup not found: {backup}"),
+            DoltError::InvalidArgument(reason) => write!(f, "Invalid argument: {reason}"),
+            DoltError::UncommittedChanges(tables) => write!(f, "Uncommitted changes: {tables}"),
+            DoltError::Io(e) => write!(f, "Error reading or writing a file: {e}"),
+            DoltError::WrongBranch { expected, actual } => {
+                write!(f, "Expected to be on branch {expected} but on {actual}")
+            }
+            #[cfg(feature = "r2d2")]
+            DoltError::Pool(e) => write!(f, "Error getting a pooled connection: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for DoltError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DoltError::Connection(e) => Some(e),
+            DoltError::Procedure { source, .. } => Some(source),
+            DoltError::Query(e) => Some(e),
+            DoltError::Io(e) => Some(e),
+            #[cfg(feature = "r2d2")]
+            DoltError::Pool(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ConnectionError> for DoltError {
+    fn from(error: ConnectionError) -> Self {
+        DoltError::Connection(error)
+    }
+}
+
+impl From<Error> for DoltError {
+    fn from(error: Error) -> Self {
+        DoltError::Query(error)
+    }
+}
+
+impl From<std::io::Error> for DoltError {
+    fn from(error: std::io::Error) -> Self {
+        DoltError::Io(error)
+    }
+}
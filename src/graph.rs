@@ -0,0 +1,490 @@
+//! The commit graph, from `dolt_commits` and `dolt_commit_ancestors`.
+//!
+//! `dolt_log` lists the commits of one branch by date, which flattens merges
+//! and hides who descends from whom. The graph keeps every commit's parents,
+//! so it can answer ancestry questions and draw the history the way
+//! `git log --graph` does:
+//!
+//! ```text
+//! *  merge rename_team
+//! |\
+//! | *  Renamed Sales to Revenue
+//! * |  Renamed Sales to Business Development
+//! |/
+//! *  Created tables
+//! ```
+
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use diesel::{
+    sql_query, sql_types::Text, ExpressionMethods, MysqlConnection, QueryDsl, RunQueryDsl,
+    SelectableHelper,
+};
+
+use crate::{
+    as_of::Revision,
+    custom_schema::{dolt_commit_ancestors, dolt_commits},
+    error::DoltError,
+    models::{DoltCommit, RevisionHash},
+};
+
+/// A commit and its parents.
+#[derive(Debug, Clone)]
+pub struct CommitNode {
+    pub commit: DoltCommit,
+    /// Parent hashes, the first parent (the branch merged into) first.
+    pub parents: Vec<String>,
+}
+
+impl CommitNode {
+    /// Whether the commit has more than one parent.
+    pub fn is_merge(&self) -> bool {
+        self.parents.len() > 1
+    }
+}
+
+/// Every commit of a database, whichever branch it is on.
+#[derive(Debug, Clone)]
+pub struct CommitGraph {
+    nodes: HashMap<String, CommitNode>,
+}
+
+impl CommitGraph {
+    /// Loads all commits and their parents.
+    pub fn load(conn: &mut MysqlConnection) -> Result<Self, DoltError> {
+        let commits = dolt_commits::table
+            .select(DoltCommit::as_select())
+            .load::<DoltCommit>(conn)?;
+
+        // The first commit has a row without a parent.
+        let edges = dolt_commit_ancestors::table
+            .select((
+                dolt_commit_ancestors::commit_hash,
+                dolt_commit_ancestors::parent_hash,
+            ))
+            .filter(dolt_commit_ancestors::parent_hash.is_not_null())
+            .order((
+                dolt_commit_ancestors::commit_hash.asc(),
+                dolt_commit_ancestors::parent_index.asc(),
+            ))
+            .load::<(String, String)>(conn)?;
+
+        let mut nodes: HashMap<String, CommitNode> = commits
+            .into_iter()
+            .map(|commit| {
+                let node = CommitNode {
+                    commit,
+                    parents: Vec::new(),
+                };
+                (node.commit.commit_hash.clone(), node)
+            })
+            .collect();
+
+        for (commit_hash, parent_hash) in edges {
+            if let Some(node) = nodes.get_mut(&commit_hash) {
+                node.parents.push(parent_hash);
+            }
+        }
+
+        Ok(CommitGraph { nodes })
+    }
+
+    /// The commit with hash `commit_hash`.
+    pub fn get(&self, commit_hash: &str) -> Option<&CommitNode> {
+        self.nodes.get(commit_hash)
+    }
+
+    /// The number of commits in the graph.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Whether the graph has no commits.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// `head` and every commit it descends from.
+    pub fn ancestors<'a>(&'a self, head: &'a str) -> HashSet<&'a str> {
+        let mut seen = HashSet::new();
+        let mut pending = vec![head];
+
+        while let Some(hash) = pending.pop() {
+            if !seen.insert(hash) {
+                continue;
+            }
+
+            if let Some(node) = self.nodes.get(hash) {
+                pending.extend(node.parents.iter().map(String::as_str));
+            }
+        }
+
+        seen
+    }
+
+    /// `head` and the commits reached by following first parents only,
+    /// newest first: the history of the branch itself, without what was
+    /// merged into it.
+    pub fn first_parents<'a>(&'a self, head: &'a str) -> Vec<&'a str> {
+        let mut chain = vec![head];
+
+        while let Some(parent) = chain
+            .last()
+            .and_then(|hash| self.nodes.get(*hash))
+            .and_then(|node| node.parents.first())
+        {
+            chain.push(parent);
+        }
+
+        chain
+    }
+
+    /// Whether `descendant` is `ancestor` or descends from it.
+    pub fn is_ancestor(&self, ancestor: &str, descendant: &str) -> bool {
+        self.ancestors(descendant).contains(ancestor)
+    }
+
+    /// How many commits `local` has that `upstream` doesn't, and the other
+    /// way around.
+    pub fn ahead_behind(&self, local: &str, upstream: &str) -> (usize, usize) {
+        let local = self.ancestors(local);
+        let upstream = self.ancestors(upstream);
+
+        (
+            local.difference(&upstream).count(),
+            upstream.difference(&local).count(),
+        )
+    }
+
+    /// The newest commit both `left` and `right` descend from, like
+    /// `DOLT_MERGE_BASE` but without a round trip.
+    pub fn merge_base<'a>(&'a self, left: &'a str, right: &'a str) -> Option<&'a str> {
+        // The log puts every commit before its parents, so the first shared
+        // commit can't be an ancestor of another shared one.
+        let right = self.ancestors(right);
+        self.log(left)
+            .into_iter()
+            .map(|node| node.commit.commit_hash.as_str())
+            .find(|hash| right.contains(hash))
+    }
+
+    /// The commits reachable from `head`, newest first, every commit before
+    /// its parents.
+    pub fn log(&self, head: &str) -> Vec<&CommitNode> {
+        /*
+        Dates alone can put a parent above its child when clocks disagree.
+        Only hand out a commit once all of its children have been, picking
+        the newest of those that are ready.
+        */
+
+        let reachable = self.ancestors(head);
+        let mut children: HashMap<&str, usize> = HashMap::new();
+        for hash in &reachable {
+            if let Some(node) = self.nodes.get(*hash) {
+                for parent in &node.parents {
+                    *children.entry(parent.as_str()).or_default() += 1;
+                }
+            }
+        }
+
+        let mut ready: BinaryHeap<_> = reachable
+            .iter()
+            .filter(|hash| !children.contains_key(*hash))
+            .filter_map(|hash| self.nodes.get(*hash))
+            .map(|node| (node.commit.date, node.commit.commit_hash.as_str()))
+            .collect();
+
+        let mut log = Vec::with_capacity(reachable.len());
+        while let Some((_, hash)) = ready.pop() {
+            let node = &self.nodes[hash];
+            log.push(node);
+
+            for parent in &node.parents {
+                let pending = children.entry(parent.as_str()).or_default();
+                *pending -= 1;
+                if *pending == 0 {
+                    if let Some(parent) = self.nodes.get(parent) {
+                        ready.push((parent.commit.date, parent.commit.commit_hash.as_str()));
+                    }
+                }
+            }
+        }
+
+        log
+    }
+
+    /// Draws the commits reachable from `head` with branch and merge lines,
+    /// `label` giving the text after each commit's `*`.
+    pub fn render(&self, head: &str, label: impl Fn(&CommitNode) -> String) -> Vec<String> {
+        /*
+        Every lane holds the commit it is waiting for. A commit takes the
+        leftmost lane waiting for it, folds the other lanes waiting for it
+        into that one and hands its lane to its first parent; further parents
+        open new lanes to its right.
+        */
+
+        let mut lines = Vec::new();
+        let mut lanes: Vec<&str> = Vec::new();
+
+        for node in self.log(head) {
+            let hash = node.commit.commit_hash.as_str();
+            let column = match lanes.iter().position(|lane| *lane == hash) {
+                Some(column) => column,
+                None => {
+                    lanes.push(hash);
+                    lanes.len() - 1
+                }
+            };
+
+            while let Some(merged) = lanes
+                .iter()
+                .skip(column + 1)
+                .position(|lane| *lane == hash)
+                .map(|offset| column + 1 + offset)
+            {
+                lines.push(edge_line((0..lanes.len()).map(|lane| {
+                    if lane < merged {
+                        (lane, '|')
+                    } else {
+                        (lane, '/')
+                    }
+                })));
+                lanes.remove(merged);
+            }
+
+            let row: Vec<&str> = (0..lanes.len())
+                .map(|lane| if lane == column { "*" } else { "|" })
+                .collect();
+            lines.push(format!("{}  {}", row.join(" "), label(node)));
+
+            match node.parents.split_first() {
+                Some((first, rest)) => {
+                    let width = lanes.len();
+                    lanes[column] = first;
+                    for (offset, parent) in rest.iter().enumerate() {
+                        lanes.insert(column + 1 + offset, parent);
+                    }
+
+                    if !rest.is_empty() {
+                        lines.push(edge_line(
+                            (0..width)
+                                .map(|lane| {
+                                    if lane <= column {
+                                        (lane, '|')
+                                    } else {
+                                        (lane, '\\')
+                                    }
+                                })
+                                .chain([(column, '\\')]),
+                        ));
+                    }
+                }
+                None => {
+                    let width = lanes.len();
+                    lanes.remove(column);
+
+                    if column + 1 < width {
+                        lines.push(edge_line((0..width).filter(|lane| *lane != column).map(
+                            |lane| {
+                                if lane < column {
+                                    (lane, '|')
+                                } else {
+                                    (lane, '/')
+                                }
+                            },
+                        )));
+                    }
+                }
+            }
+        }
+
+        lines
+    }
+}
+
+/// A line between two rows of the graph. `|` stays in its lane, `/` and `\`
+/// sit between the lane and its left or right neighbour.
+fn edge_line(glyphs: impl IntoIterator<Item = (usize, char)>) -> String {
+    let mut line = Vec::new();
+
+    for (lane, glyph) in glyphs {
+        let at = match glyph {
+            '/' => 2 * lane - 1,
+            '\\' => 2 * lane + 1,
+            _ => 2 * lane,
+        };
+        if line.len() <= at {
+            line.resize(at + 1, ' ');
+        }
+        line[at] = glyph;
+    }
+
+    line.into_iter().collect()
+}
+
+/// The commit hash `revision` resolves to.
+pub fn hash_of(
+    conn: &mut MysqlConnection,
+    revision: impl Into<Revision>,
+) -> Result<String, DoltError> {
+    let revision = revision.into().to_arg();
+    let result = sql_query("SELECT HASHOF(?) AS hash")
+        .bind::<Text, _>(&revision)
+        .load::<RevisionHash>(conn)
+        .map_err(|e| DoltError::from_procedure("HASHOF", &revision, e))?;
+
+    result
+        .into_iter()
+        .next()
+        .map(|revision_hash| revision_hash.hash)
+        .ok_or(DoltError::Query(diesel::result::Error::NotFound))
+}
+
+/// The best common ancestor of `left` and `right`.
+pub fn merge_base(
+    conn: &mut MysqlConnection,
+    left: impl Into<Revision>,
+    right: impl Into<Revision>,
+) -> Result<String, DoltError> {
+    let left = left.into().to_arg();
+    let right = right.into().to_arg();
+    let result = sql_query("SELECT DOLT_MERGE_BASE(?, ?) AS hash")
+        .bind::<Text, _>(&left)
+        .bind::<Text, _>(&right)
+        .load::<RevisionHash>(conn)
+        .map_err(|e| DoltError::from_procedure("DOLT_MERGE_BASE", &left, e))?;
+
+    result
+        .into_iter()
+        .next()
+        .map(|revision_hash| revision_hash.hash)
+        .ok_or(DoltError::Query(diesel::result::Error::NotFound))
+}
+
+/// Whether `descendant` is `ancestor` or descends from it.
+pub fn is_ancestor(
+    conn: &mut MysqlConnection,
+    ancestor: impl Into<Revision>,
+    descendant: impl Into<Revision>,
+) -> Result<bool, DoltError> {
+    let ancestor = hash_of(conn, ancestor)?;
+    let base = merge_base(conn, ancestor.as_str(), descendant)?;
+
+    Ok(base == ancestor)
+}
+
+/// How many commits `branch` has that `upstream` doesn't, and the other way
+/// around.
+pub fn ahead_behind(
+    conn: &mut MysqlConnection,
+    branch: impl Into<Revision>,
+    upstream: impl Into<Revision>,
+) -> Result<(usize, usize), DoltError> {
+    let branch = hash_of(conn, branch)?;
+    let upstream = hash_of(conn, upstream)?;
+
+    Ok(CommitGraph::load(conn)?.ahead_behind(&branch, &upstream))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a - b - d - m      main
+    ///  \        /
+    ///   c ----- + - e    feature, which went on after the merge
+    fn graph() -> CommitGraph {
+        let commits = [
+            ("a", 1, vec![]),
+            ("b", 2, vec!["a"]),
+            ("c", 3, vec!["a"]),
+            ("d", 4, vec!["b"]),
+            ("m", 5, vec!["d", "c"]),
+            ("e", 6, vec!["c"]),
+        ];
+
+        let nodes = commits
+            .into_iter()
+            .map(|(hash, day, parents)| {
+                let node = CommitNode {
+                    commit: DoltCommit {
+                        commit_hash: hash.to_string(),
+                        committer: "Tim".to_string(),
+                        email: "tim@dolthub.com".to_string(),
+                        date: chrono::NaiveDate::from_ymd_opt(2023, 7, day)
+                            .and_then(|date| date.and_hms_opt(12, 0, 0))
+                            .unwrap(),
+                        message: format!("commit {hash}"),
+                    },
+                    parents: parents.into_iter().map(str::to_string).collect(),
+                };
+                (hash.to_string(), node)
+            })
+            .collect();
+
+        CommitGraph { nodes }
+    }
+
+    #[test]
+    fn finds_the_merge_base() {
+        let graph = graph();
+
+        assert_eq!(graph.merge_base("m", "e"), Some("c"));
+        assert_eq!(graph.merge_base("d", "c"), Some("a"));
+        assert_eq!(graph.merge_base("m", "d"), Some("d"));
+        assert_eq!(graph.merge_base("m", "unknown"), None);
+    }
+
+    #[test]
+    fn counts_ahead_and_behind() {
+        let graph = graph();
+
+        assert_eq!(graph.ahead_behind("m", "e"), (3, 1));
+        assert_eq!(graph.ahead_behind("e", "m"), (1, 3));
+        assert_eq!(graph.ahead_behind("m", "m"), (0, 0));
+        assert!(graph.is_ancestor("c", "m"));
+        assert!(!graph.is_ancestor("d", "e"));
+    }
+
+    #[test]
+    fn follows_first_parents() {
+        assert_eq!(graph().first_parents("m"), ["m", "d", "b", "a"]);
+    }
+
+    #[test]
+    fn logs_children_before_parents() {
+        let graph = graph();
+        let log: Vec<&str> = graph
+            .log("m")
+            .into_iter()
+            .map(|node| node.commit.commit_hash.as_str())
+            .collect();
+
+        assert_eq!(log, ["m", "d", "c", "b", "a"]);
+    }
+
+    #[test]
+    fn renders_forks_and_merges() {
+        let lines = graph().render("m", |node| node.commit.message.clone());
+
+        assert_eq!(
+            lines,
+            [
+                "*  commit m",
+                "|\\",
+                "* |  commit d",
+                "| *  commit c",
+                "* |  commit b",
+                "|/",
+                "*  commit a",
+            ]
+        );
+    }
+
+    #[test]
+    fn places_edge_glyphs_between_lanes() {
+        assert_eq!(edge_line([(0, '|'), (1, '/')]), "|/");
+        assert_eq!(edge_line([(0, '|'), (1, '|'), (1, '\\')]), "| |\\");
+        assert_eq!(edge_line([(2, '|')]), "    |");
+    }
+}
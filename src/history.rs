@@ -0,0 +1,182 @@
+//! Every version of a row, read from the `dolt_history_<table>` system tables.
+//!
+//! `dolt_history_<table>` has one row per commit reachable from HEAD for
+//! every row that existed at that commit. [`history_of`] collapses that into
+//! the versions where the row actually changed:
+//!
+//! ```no_run
+//! # use dolt_dieselrs_getting_started::{history::history_of, models::Employee, DoltError};
+//! # fn run(conn: &mut diesel::MysqlConnection) -> Result<(), DoltError> {
+//! for version in history_of::<Employee>(conn, 0)? {
+//!     println!("{}: {}", version.commit_hash, version.row.first_name);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use diesel::{
+    ExpressionMethods, MysqlConnection, NullableExpressionMethods, QueryDsl, RunQueryDsl,
+};
+
+use crate::{
+    custom_schema::{dolt_history_employees, dolt_history_employees_teams, dolt_history_teams},
+    error::DoltError,
+    models::{Employee, EmployeeAfterEdit, EmployeeTeam, Team},
+};
+
+/// A row as it was at a commit.
+#[derive(Debug, PartialEq)]
+pub struct HistoryEntry<M> {
+    pub row: M,
+    pub commit_hash: String,
+    pub committer: String,
+    pub commit_date: chrono::NaiveDateTime,
+}
+
+impl<M> From<(M, String, String, chrono::NaiveDateTime)> for HistoryEntry<M> {
+    fn from(
+        (row, commit_hash, committer, commit_date): (M, String, String, chrono::NaiveDateTime),
+    ) -> Self {
+        HistoryEntry {
+            row,
+            commit_hash,
+            committer,
+            commit_date,
+        }
+    }
+}
+
+/// Models backed by a table that has a `dolt_history_<table>` system table.
+pub trait DoltHistory: PartialEq + Sized {
+    /// The primary key used to pick the row.
+    type Id;
+
+    /// The row with the given id at every commit it exists in, oldest first.
+    fn load_history(
+        conn: &mut MysqlConnection,
+        id: Self::Id,
+    ) -> Result<Vec<HistoryEntry<Self>>, DoltError>;
+}
+
+/// The versions of the row with the given id, oldest first.
+///
+/// A commit only shows up when the row differs from the previous version,
+/// as far as the fields of `M` go.
+pub fn history_of<M: DoltHistory>(
+    conn: &mut MysqlConnection,
+    id: M::Id,
+) -> Result<Vec<HistoryEntry<M>>, DoltError> {
+    let mut versions: Vec<HistoryEntry<M>> = Vec::new();
+
+    for entry in M::load_history(conn, id)? {
+        if versions.last().map(|last| &last.row) != Some(&entry.row) {
+            versions.push(entry);
+        }
+    }
+
+    Ok(versions)
+}
+
+impl DoltHistory for Employee {
+    type Id = i32;
+
+    fn load_history(
+        conn: &mut MysqlConnection,
+        id: i32,
+    ) -> Result<Vec<HistoryEntry<Self>>, DoltError> {
+        use dolt_history_employees::dsl;
+
+        let results = dsl::dolt_history_employees
+            .select((
+                (
+                    dsl::id,
+                    dsl::last_name.assume_not_null(),
+                    dsl::first_name.assume_not_null(),
+                ),
+                dsl::commit_hash,
+                dsl::committer,
+                dsl::commit_date,
+            ))
+            .filter(dsl::id.eq(id))
+            .order(dsl::commit_date.asc())
+            .load::<(Employee, String, String, chrono::NaiveDateTime)>(conn)
+            .map_err(|e| DoltError::from_table_query("dolt_history_employees", e))?;
+
+        Ok(results.into_iter().map(HistoryEntry::from).collect())
+    }
+}
+
+impl DoltHistory for EmployeeAfterEdit {
+    type Id = i32;
+
+    fn load_history(
+        conn: &mut MysqlConnection,
+        id: i32,
+    ) -> Result<Vec<HistoryEntry<Self>>, DoltError> {
+        use dolt_history_employees::dsl;
+
+        let results = dsl::dolt_history_employees
+            .select((
+                (dsl::id, dsl::last_name, dsl::first_name, dsl::start_date),
+                dsl::commit_hash,
+                dsl::committer,
+                dsl::commit_date,
+            ))
+            .filter(dsl::id.eq(id))
+            .order(dsl::commit_date.asc())
+            .load::<(EmployeeAfterEdit, String, String, chrono::NaiveDateTime)>(conn)
+            .map_err(|e| DoltError::from_table_query("dolt_history_employees", e))?;
+
+        Ok(results.into_iter().map(HistoryEntry::from).collect())
+    }
+}
+
+impl DoltHistory for Team {
+    type Id = i32;
+
+    fn load_history(
+        conn: &mut MysqlConnection,
+        id: i32,
+    ) -> Result<Vec<HistoryEntry<Self>>, DoltError> {
+        use dolt_history_teams::dsl;
+
+        let results = dsl::dolt_history_teams
+            .select((
+                (dsl::id, dsl::name.assume_not_null()),
+                dsl::commit_hash,
+                dsl::committer,
+                dsl::commit_date,
+            ))
+            .filter(dsl::id.eq(id))
+            .order(dsl::commit_date.asc())
+            .load::<(Team, String, String, chrono::NaiveDateTime)>(conn)
+            .map_err(|e| DoltError::from_table_query("dolt_history_teams", e))?;
+
+        Ok(results.into_iter().map(HistoryEntry::from).collect())
+    }
+}
+
+impl DoltHistory for EmployeeTeam {
+    type Id = i32;
+
+    fn load_history(
+        conn: &mut MysqlConnection,
+        id: i32,
+    ) -> Result<Vec<HistoryEntry<Self>>, DoltError> {
+        use dolt_history_employees_teams::dsl;
+
+        let results = dsl::dolt_history_employees_teams
+            .select((
+                (dsl::id, dsl::employee_id, dsl::team_id),
+                dsl::commit_hash,
+                dsl::committer,
+                dsl::commit_date,
+            ))
+            .filter(dsl::id.eq(id))
+            .order(dsl::commit_date.asc())
+            .load::<(EmployeeTeam, String, String, chrono::NaiveDateTime)>(conn)
+            .map_err(|e| DoltError::from_table_query("dolt_history_employees_teams", e))?;
+
+        Ok(results.into_iter().map(HistoryEntry::from).collect())
+    }
+}
@@ -0,0 +1,77 @@
+//! Dolt version control from Rust, on top of Diesel's MySQL backend.
+//!
+//! [`DoltRepo`] owns a connection to a Dolt sql-server and exposes commit,
+//! branch, checkout, merge, reset, log, status and diff as methods. Each
+//! repo is bound to one branch through [`connection`], so several branches
+//! can be open at the same time. The
+//! Dolt system tables are available as Diesel `table!` definitions in
+//! [`custom_schema`] and their rows as structs in [`models`].
+//!
+//! ```no_run
+//! use diesel::{sql_query, RunQueryDsl};
+//! use dolt_dieselrs_getting_started::{DoltError, DoltRepo};
+//!
+//! fn main() -> Result<(), DoltError> {
+//!     let mut repo = DoltRepo::connect("mysql://root:@127.0.0.1:3306/dieselrs_big_demo")?;
+//!     repo.create_branch("feature")?;
+//!     repo.checkout("feature")?;
+//!     sql_query("INSERT INTO teams VALUES (3, 'Support')").execute(repo.connection())?;
+//!     repo.commit("Tim <tim@dolthub.com>", "Added Support")?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! `#[derive(DoltDiff)]` on a model generates the row type of its
+//! `dolt_diff_<table>` system table; see [`DoltDiff`]. [`diff`] compares any
+//! two revisions, not just the working set.
+//!
+//! Conflicting merges can be inspected and resolved with [`conflicts`].
+//!
+//! Any table can be read at an older revision with [`as_of::AsOfDsl`], and
+//! [`history::history_of`] lists every version of a single row.
+//!
+//! With the `r2d2` feature (on by default) [`pool::DoltPool`] hands out
+//! pooled connections that are already bound to a requested branch.
+//!
+//! The `tabled` feature adds the `print` module for rendering wide system
+//! table rows. The `cli` feature, which the demo binary needs, turns it on
+//! along with `dotenv`.
+
+// Lets code generated by the derives name this crate from inside it, too.
+extern crate self as dolt_dieselrs_getting_started;
+
+pub mod as_of;
+pub mod backup;
+pub mod blame;
+pub mod column_diff;
+pub mod conflicts;
+pub mod connection;
+/// Diesel definitions of the Dolt system tables.
+pub mod custom_schema;
+pub mod diff;
+/// The [`DoltError`] type.
+pub mod error;
+pub mod graph;
+pub mod history;
+pub mod log;
+/// Row types for the system tables and the demo tables.
+pub mod models;
+pub mod patch;
+#[cfg(feature = "r2d2")]
+pub mod pool;
+#[cfg(feature = "tabled")]
+pub mod print;
+/// Injection-safe calls to the `DOLT_*` stored procedures.
+pub mod procedure;
+pub mod rebase;
+pub mod remote;
+/// The [`DoltRepo`] handle.
+pub mod repo;
+/// Diesel definitions of the demo tables (generated by the Diesel CLI).
+pub mod schema;
+pub mod schema_diff;
+
+pub use dolt_dieselrs_derive::{DoltConflict, DoltDiff};
+pub use error::DoltError;
+pub use procedure::DoltProcedure;
+pub use repo::DoltRepo;
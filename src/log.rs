@@ -0,0 +1,390 @@
+//! Filtered, paginated commit logs over the `DOLT_LOG()` table function.
+//!
+//! ```no_run
+//! # use dolt_dieselrs_getting_started::{log::LogQuery, DoltError, DoltRepo};
+//! # fn run(repo: &mut DoltRepo) -> Result<(), DoltError> {
+//! let page = LogQuery::new()
+//!     .range("main", "modify_data")
+//!     .author("tim@dolthub.com")
+//!     .touching_table("employees")
+//!     .limit(10)
+//!     .load(repo.connection())?;
+//!
+//! if let Some(last) = page.last() {
+//!     let next = LogQuery::new()
+//!         .range("main", "modify_data")
+//!         .author("tim@dolthub.com")
+//!         .touching_table("employees")
+//!         .after(last)
+//!         .limit(10)
+//!         .load(repo.connection())?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use diesel::{
+    mysql::Mysql,
+    sql_query,
+    sql_types::{Text, Timestamp},
+    MysqlConnection, RunQueryDsl,
+};
+
+use crate::{
+    as_of::Revision,
+    error::DoltError,
+    graph::{hash_of, CommitGraph},
+    models::DoltLogEntry,
+};
+
+/// The last entry of a page; the next page starts right after it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogCursor {
+    pub date: chrono::NaiveDateTime,
+    pub commit_hash: String,
+}
+
+impl From<&DoltLogEntry> for LogCursor {
+    fn from(entry: &DoltLogEntry) -> Self {
+        LogCursor {
+            date: entry.date,
+            commit_hash: entry.commit_hash.clone(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Bind {
+    Text(String),
+    Timestamp(chrono::NaiveDateTime),
+}
+
+/// Builds a `DOLT_LOG()` query. Entries come newest first, ties broken by
+/// commit hash so pages never overlap.
+#[derive(Debug, Clone, Default)]
+pub struct LogQuery {
+    /// The revision to log, with the revision whose history is left out.
+    revision: Option<(Option<String>, String)>,
+    author: Option<String>,
+    since: Option<chrono::NaiveDateTime>,
+    until: Option<chrono::NaiveDateTime>,
+    message: Option<String>,
+    table: Option<String>,
+    first_parent: bool,
+    after: Option<LogCursor>,
+    limit: Option<u64>,
+    offset: Option<u64>,
+}
+
+impl LogQuery {
+    /// The log of the current branch.
+    pub fn new() -> Self {
+        LogQuery::default()
+    }
+
+    /// Log `revision` instead of the current branch.
+    pub fn revision(mut self, revision: impl Into<Revision>) -> Self {
+        self.revision = Some((None, revision.into().to_arg()));
+        self
+    }
+
+    /// Commits reachable from `to` but not from `from`, like `from..to`.
+    pub fn range(mut self, from: impl Into<Revision>, to: impl Into<Revision>) -> Self {
+        self.revision = Some((Some(from.into().to_arg()), to.into().to_arg()));
+        self
+    }
+
+    /// Commits whose committer name or email contains `author`.
+    pub fn author(mut self, author: &str) -> Self {
+        self.author = Some(author.to_string());
+        self
+    }
+
+    /// Commits made at or after `since`.
+    pub fn since(mut self, since: chrono::NaiveDateTime) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    /// Commits made before `until`.
+    pub fn until(mut self, until: chrono::NaiveDateTime) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    /// Commits whose message contains `message`.
+    pub fn message_contains(mut self, message: &str) -> Self {
+        self.message = Some(message.to_string());
+        self
+    }
+
+    /// Commits that changed `table`, its data or its schema, within the
+    /// revision being logged.
+    pub fn touching_table(mut self, table: &str) -> Self {
+        self.table = Some(table.to_string());
+        self
+    }
+
+    /// Follow only the first parent of merges, leaving out the commits
+    /// that came in through them.
+    pub fn first_parent(mut self) -> Self {
+        self.first_parent = true;
+        self
+    }
+
+    /// Start after `cursor`, usually the last entry of the previous page.
+    pub fn after(mut self, cursor: impl Into<LogCursor>) -> Self {
+        self.after = Some(cursor.into());
+        self
+    }
+
+    /// Return at most `limit` entries.
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Skip the first `offset` entries.
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Runs the query.
+    pub fn load(&self, conn: &mut MysqlConnection) -> Result<Vec<DoltLogEntry>, DoltError> {
+        let first_parents = if self.first_parent {
+            Some(self.first_parent_hashes(conn)?)
+        } else {
+            None
+        };
+        let (stmt, binds) = self.statement(first_parents);
+
+        let mut query = sql_query(stmt).into_boxed::<Mysql>();
+        for bind in binds {
+            query = match bind {
+                Bind::Text(value) => query.bind::<Text, _>(value),
+                Bind::Timestamp(value) => query.bind::<Timestamp, _>(value),
+            };
+        }
+
+        query
+            .load::<DoltLogEntry>(conn)
+            .map_err(|e| DoltError::from_table_query("dolt_log", e))
+    }
+
+    /// The SQL and its binds, `first_parents` being the commits
+    /// [`first_parent`](Self::first_parent) keeps.
+    fn statement(&self, first_parents: Option<Vec<String>>) -> (String, Vec<Bind>) {
+        let mut binds = Vec::new();
+        let mut conditions = Vec::new();
+
+        let mut args = Vec::new();
+        match &self.revision {
+            Some((Some(excluded), revision)) => args.push(format!("{excluded}..{revision}")),
+            Some((None, revision)) => args.push(revision.clone()),
+            None => {}
+        }
+        // DOLT_LOG filters on the logged revision's own diffs, where
+        // dolt_diff would only know the commits reachable from HEAD.
+        if let Some(table) = &self.table {
+            args.push("--tables".to_string());
+            args.push(table.clone());
+        }
+        let from = format!("DOLT_LOG({})", vec!["?"; args.len()].join(", "));
+        binds.extend(args.into_iter().map(Bind::Text));
+
+        if let Some(author) = &self.author {
+            conditions.push("(LOCATE(?, committer) > 0 OR LOCATE(?, email) > 0)".to_string());
+            binds.push(Bind::Text(author.clone()));
+            binds.push(Bind::Text(author.clone()));
+        }
+        if let Some(since) = self.since {
+            conditions.push("date >= ?".to_string());
+            binds.push(Bind::Timestamp(since));
+        }
+        if let Some(until) = self.until {
+            conditions.push("date < ?".to_string());
+            binds.push(Bind::Timestamp(until));
+        }
+        if let Some(message) = &self.message {
+            conditions.push("LOCATE(?, message) > 0".to_string());
+            binds.push(Bind::Text(message.clone()));
+        }
+        if let Some(hashes) = first_parents {
+            conditions.push(format!(
+                "commit_hash IN ({})",
+                vec!["?"; hashes.len()].join(", ")
+            ));
+            binds.extend(hashes.into_iter().map(Bind::Text));
+        }
+        if let Some(cursor) = &self.after {
+            conditions.push("(date < ? OR (date = ? AND commit_hash < ?))".to_string());
+            binds.push(Bind::Timestamp(cursor.date));
+            binds.push(Bind::Timestamp(cursor.date));
+            binds.push(Bind::Text(cursor.commit_hash.clone()));
+        }
+
+        let mut stmt = format!("SELECT commit_hash, committer, email, date, message FROM {from}");
+        if !conditions.is_empty() {
+            stmt.push_str(" WHERE ");
+            stmt.push_str(&conditions.join(" AND "));
+        }
+        stmt.push_str(" ORDER BY date DESC, commit_hash DESC");
+        match (self.limit, self.offset) {
+            (Some(limit), Some(offset)) => {
+                stmt.push_str(&format!(" LIMIT {limit} OFFSET {offset}"))
+            }
+            (Some(limit), None) => stmt.push_str(&format!(" LIMIT {limit}")),
+            // MySQL only takes an OFFSET after a LIMIT.
+            (None, Some(offset)) => stmt.push_str(&format!(" LIMIT {} OFFSET {offset}", u64::MAX)),
+            (None, None) => {}
+        }
+
+        (stmt, binds)
+    }
+
+    fn first_parent_hashes(&self, conn: &mut MysqlConnection) -> Result<Vec<String>, DoltError> {
+        let revision = match &self.revision {
+            Some((_, revision)) => revision.as_str(),
+            None => "HEAD",
+        };
+        let head = hash_of(conn, revision)?;
+        let graph = CommitGraph::load(conn)?;
+
+        Ok(graph
+            .first_parents(&head)
+            .into_iter()
+            .map(str::to_string)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SELECT: &str = "SELECT commit_hash, committer, email, date, message FROM";
+    const ORDER: &str = "ORDER BY date DESC, commit_hash DESC";
+
+    fn day(day: u32) -> chrono::NaiveDateTime {
+        chrono::NaiveDate::from_ymd_opt(2023, 7, day)
+            .and_then(|date| date.and_hms_opt(12, 0, 0))
+            .unwrap()
+    }
+
+    fn text(value: &str) -> Bind {
+        Bind::Text(value.to_string())
+    }
+
+    #[test]
+    fn logs_the_current_branch_without_filters() {
+        let (stmt, binds) = LogQuery::new().statement(None);
+
+        assert_eq!(stmt, format!("{SELECT} DOLT_LOG() {ORDER}"));
+        assert!(binds.is_empty());
+    }
+
+    #[test]
+    fn passes_the_revision_and_table_to_dolt_log() {
+        let (stmt, binds) = LogQuery::new().revision("feature").statement(None);
+        assert_eq!(stmt, format!("{SELECT} DOLT_LOG(?) {ORDER}"));
+        assert_eq!(binds, [text("feature")]);
+
+        let (stmt, binds) = LogQuery::new()
+            .range("main", "feature")
+            .touching_table("employees")
+            .statement(None);
+        assert_eq!(stmt, format!("{SELECT} DOLT_LOG(?, ?, ?) {ORDER}"));
+        assert_eq!(
+            binds,
+            [text("main..feature"), text("--tables"), text("employees")]
+        );
+    }
+
+    #[test]
+    fn filters_on_author_dates_and_message() {
+        let (stmt, binds) = LogQuery::new()
+            .author("tim")
+            .since(day(1))
+            .until(day(8))
+            .message_contains("team")
+            .statement(None);
+
+        assert_eq!(
+            stmt,
+            format!(
+                "{SELECT} DOLT_LOG() WHERE (LOCATE(?, committer) > 0 OR LOCATE(?, email) > 0) \
+                 AND date >= ? AND date < ? AND LOCATE(?, message) > 0 {ORDER}"
+            )
+        );
+        assert_eq!(
+            binds,
+            [
+                text("tim"),
+                text("tim"),
+                Bind::Timestamp(day(1)),
+                Bind::Timestamp(day(8)),
+                text("team"),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_only_the_first_parents() {
+        let (stmt, binds) = LogQuery::new()
+            .first_parent()
+            .statement(Some(vec!["m".to_string(), "d".to_string()]));
+
+        assert_eq!(
+            stmt,
+            format!("{SELECT} DOLT_LOG() WHERE commit_hash IN (?, ?) {ORDER}")
+        );
+        assert_eq!(binds, [text("m"), text("d")]);
+    }
+
+    #[test]
+    fn pages_with_a_cursor() {
+        let cursor = LogCursor {
+            date: day(3),
+            commit_hash: "c".to_string(),
+        };
+        let (stmt, binds) = LogQuery::new()
+            .revision("main")
+            .after(cursor)
+            .limit(10)
+            .statement(None);
+
+        assert_eq!(
+            stmt,
+            format!(
+                "{SELECT} DOLT_LOG(?) WHERE (date < ? OR (date = ? AND commit_hash < ?)) \
+                 {ORDER} LIMIT 10"
+            )
+        );
+        assert_eq!(
+            binds,
+            [
+                text("main"),
+                Bind::Timestamp(day(3)),
+                Bind::Timestamp(day(3)),
+                text("c"),
+            ]
+        );
+    }
+
+    #[test]
+    fn pages_with_an_offset() {
+        let (stmt, binds) = LogQuery::new().limit(10).offset(20).statement(None);
+        assert_eq!(
+            stmt,
+            format!("{SELECT} DOLT_LOG() {ORDER} LIMIT 10 OFFSET 20")
+        );
+        assert!(binds.is_empty());
+
+        let (stmt, _) = LogQuery::new().offset(20).statement(None);
+        assert_eq!(
+            stmt,
+            format!("{SELECT} DOLT_LOG() {ORDER} LIMIT {} OFFSET 20", u64::MAX)
+        );
+    }
+}
@@ -1,41 +1,51 @@
 use crate::{
-    custom_schema::{dolt_branches, dolt_log, dolt_status},
+    custom_schema::{
+        dolt_backups, dolt_branches, dolt_column_diff, dolt_commits, dolt_conflicts,
+        dolt_constraint_violations, dolt_log, dolt_remote_branches, dolt_remotes,
+        dolt_schema_conflicts, dolt_stashes, dolt_status, dolt_tags,
+    },
     schema::{employees, employees_teams, teams},
 };
 use diesel::prelude::*;
-use tabled::Tabled;
+use dolt_dieselrs_derive::{DoltConflict, DoltDiff};
 
-fn display_option<T: std::fmt::Display>(o: &Option<T>) -> String {
+/// Renders an optional value the way the diff tables do, `_` for `None`.
+pub(crate) fn display_option<T: std::fmt::Display>(o: &Option<T>) -> String {
     match o {
         Some(s) => format!("{}", s),
         None => "_".to_string(),
     }
 }
 
+/// Result of `SELECT active_branch()`.
 #[derive(Debug, QueryableByName)]
 pub struct ActiveBranch {
     #[diesel(column_name = "active_branch()", sql_type = diesel::sql_types::Text)]
     pub name: String,
 }
 
+/// A row of `SHOW tables` in the `dieselrs_big_demo` database.
 #[derive(Debug, QueryableByName)]
 pub struct Table {
     #[diesel(column_name = "Tables_in_dieselrs_big_demo", sql_type = diesel::sql_types::Text)]
     pub name: String,
 }
 
+/// The `status` row returned by most `CALL DOLT_*()` procedures.
 #[derive(Debug, QueryableByName)]
 pub struct DoltCallResponse {
     #[diesel(column_name = "status", sql_type = diesel::sql_types::Integer)]
     pub status: i32,
 }
 
+/// The row returned by `CALL DOLT_COMMIT()`.
 #[derive(Debug, QueryableByName)]
 pub struct DoltCommitResponse {
     #[diesel(column_name = "hash", sql_type = diesel::sql_types::Text)]
     pub hash: String,
 }
 
+/// The row returned by `CALL DOLT_MERGE()`.
 #[derive(Debug, QueryableByName)]
 pub struct DoltMergeResponse {
     #[diesel(column_name = "hash", sql_type = diesel::sql_types::Text)]
@@ -46,7 +56,112 @@ pub struct DoltMergeResponse {
     pub conflicts: i32,
 }
 
-#[derive(Debug, Queryable, Selectable)]
+/// The row returned by `CALL DOLT_PULL()`.
+#[derive(Debug, QueryableByName)]
+pub struct DoltPullResponse {
+    #[diesel(column_name = "fast_forward", sql_type = diesel::sql_types::Integer)]
+    pub fast_forward: i32,
+    #[diesel(column_name = "conflicts", sql_type = diesel::sql_types::Integer)]
+    pub conflicts: i32,
+}
+
+/// The row returned by `CALL DOLT_CHERRY_PICK()`.
+#[derive(Debug, QueryableByName)]
+pub struct DoltCherryPickResponse {
+    /// The new commit, missing when the pick left something to resolve.
+    #[diesel(column_name = "hash", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub hash: Option<String>,
+    #[diesel(column_name = "data_conflicts", sql_type = diesel::sql_types::Integer)]
+    pub data_conflicts: i32,
+    #[diesel(column_name = "schema_conflicts", sql_type = diesel::sql_types::Integer)]
+    pub schema_conflicts: i32,
+    #[diesel(column_name = "constraint_violations", sql_type = diesel::sql_types::Integer)]
+    pub constraint_violations: i32,
+}
+
+/// Result of `SELECT HASHOF(?) AS hash`.
+#[derive(Debug, QueryableByName)]
+pub struct RevisionHash {
+    #[diesel(column_name = "hash", sql_type = diesel::sql_types::Text)]
+    pub hash: String,
+}
+
+/// A row of the `DOLT_PATCH()` table function.
+#[derive(Debug, QueryableByName)]
+pub struct DoltPatchStatement {
+    #[diesel(column_name = "statement_order", sql_type = diesel::sql_types::Unsigned<diesel::sql_types::BigInt>)]
+    pub statement_order: u64,
+    #[diesel(column_name = "table_name", sql_type = diesel::sql_types::Text)]
+    pub table_name: String,
+    #[diesel(column_name = "diff_type", sql_type = diesel::sql_types::Text)]
+    pub diff_type: String,
+    #[diesel(column_name = "statement", sql_type = diesel::sql_types::Text)]
+    pub statement: String,
+}
+
+/// A row of the `DOLT_DIFF_STAT()` table function.
+#[derive(Debug, QueryableByName)]
+pub struct DoltDiffStat {
+    #[diesel(column_name = "table_name", sql_type = diesel::sql_types::Text)]
+    pub table_name: String,
+    #[diesel(column_name = "rows_unmodified", sql_type = diesel::sql_types::BigInt)]
+    pub rows_unmodified: i64,
+    #[diesel(column_name = "rows_added", sql_type = diesel::sql_types::BigInt)]
+    pub rows_added: i64,
+    #[diesel(column_name = "rows_deleted", sql_type = diesel::sql_types::BigInt)]
+    pub rows_deleted: i64,
+    #[diesel(column_name = "rows_modified", sql_type = diesel::sql_types::BigInt)]
+    pub rows_modified: i64,
+    #[diesel(column_name = "cells_added", sql_type = diesel::sql_types::BigInt)]
+    pub cells_added: i64,
+    #[diesel(column_name = "cells_deleted", sql_type = diesel::sql_types::BigInt)]
+    pub cells_deleted: i64,
+    #[diesel(column_name = "cells_modified", sql_type = diesel::sql_types::BigInt)]
+    pub cells_modified: i64,
+    #[diesel(column_name = "old_row_count", sql_type = diesel::sql_types::BigInt)]
+    pub old_row_count: i64,
+    #[diesel(column_name = "new_row_count", sql_type = diesel::sql_types::BigInt)]
+    pub new_row_count: i64,
+    #[diesel(column_name = "old_cell_count", sql_type = diesel::sql_types::BigInt)]
+    pub old_cell_count: i64,
+    #[diesel(column_name = "new_cell_count", sql_type = diesel::sql_types::BigInt)]
+    pub new_cell_count: i64,
+}
+
+/// A row of the `DOLT_DIFF_SUMMARY()` table function.
+#[derive(Debug, QueryableByName)]
+pub struct DoltDiffSummary {
+    /// Empty for a table that was added.
+    #[diesel(column_name = "from_table_name", sql_type = diesel::sql_types::Text)]
+    pub from_table_name: String,
+    /// Empty for a table that was dropped.
+    #[diesel(column_name = "to_table_name", sql_type = diesel::sql_types::Text)]
+    pub to_table_name: String,
+    #[diesel(column_name = "diff_type", sql_type = diesel::sql_types::Text)]
+    pub diff_type: String,
+    #[diesel(column_name = "data_change", sql_type = diesel::sql_types::Bool)]
+    pub data_change: bool,
+    #[diesel(column_name = "schema_change", sql_type = diesel::sql_types::Bool)]
+    pub schema_change: bool,
+}
+
+/// A row of the `DOLT_SCHEMA_DIFF()` table function.
+#[derive(Debug, QueryableByName)]
+pub struct DoltSchemaDiff {
+    /// Empty for a table that was created.
+    #[diesel(column_name = "from_table_name", sql_type = diesel::sql_types::Text)]
+    pub from_table_name: String,
+    /// Empty for a table that was dropped.
+    #[diesel(column_name = "to_table_name", sql_type = diesel::sql_types::Text)]
+    pub to_table_name: String,
+    #[diesel(column_name = "from_create_statement", sql_type = diesel::sql_types::Text)]
+    pub from_create_statement: String,
+    #[diesel(column_name = "to_create_statement", sql_type = diesel::sql_types::Text)]
+    pub to_create_statement: String,
+}
+
+/// A row of the `dolt_log` system table or the `DOLT_LOG()` table function.
+#[derive(Debug, Queryable, QueryableByName, Selectable)]
 #[diesel(table_name = dolt_log)]
 pub struct DoltLogEntry {
     pub commit_hash: String,
@@ -56,123 +171,34 @@ pub struct DoltLogEntry {
     pub message: String,
 }
 
-#[derive(Debug, QueryableByName, Tabled)]
-pub struct DoltDiffEmployeesEntry {
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "to_id", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
-    pub to_id: Option<i32>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "to_last_name", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
-    pub to_last_name: Option<String>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "to_first_name", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
-    pub to_first_name: Option<String>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "to_commit", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
-    pub to_commit: Option<String>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "to_commit_date", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Timestamp>)]
-    pub to_commit_date: Option<chrono::NaiveDateTime>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "from_id", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
-    pub from_id: Option<i32>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "from_last_name", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
-    pub from_last_name: Option<String>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "from_first_name", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
-    pub from_first_name: Option<String>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "from_commit", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
-    pub from_commit: Option<String>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "from_commit_date", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Timestamp>)]
-    pub from_commit_date: Option<chrono::NaiveDateTime>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "diff_type", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
-    pub diff_type: Option<String>,
-}
-
-#[derive(Debug, QueryableByName, Tabled)]
-pub struct DoltDiffEmployeesTeamsEntry {
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "to_id", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
-    pub to_id: Option<i32>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "to_employee_id", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
-    pub to_employee_id: Option<i32>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "to_team_id", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
-    pub to_team_id: Option<i32>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "to_commit", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
-    pub to_commit: Option<String>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "to_commit_date", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Timestamp>)]
-    pub to_commit_date: Option<chrono::NaiveDateTime>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "from_id", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
-    pub from_id: Option<i32>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "from_employee_id", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
-    pub from_employee_id: Option<i32>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "from_team_id", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
-    pub from_team_id: Option<i32>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "from_commit", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
-    pub from_commit: Option<String>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "from_commit_date", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Timestamp>)]
-    pub from_commit_date: Option<chrono::NaiveDateTime>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "diff_type", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
-    pub diff_type: Option<String>,
-}
-
-#[derive(Debug, QueryableByName, Tabled)]
-pub struct DoltDiffEmployeesEntryAfterEdit {
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "to_id", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
-    pub to_id: Option<i32>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "to_last_name", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
-    pub to_last_name: Option<String>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "to_first_name", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
-    pub to_first_name: Option<String>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "to_start_date", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Timestamp>)]
-    pub to_start_date: Option<chrono::NaiveDateTime>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "to_commit", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
-    pub to_commit: Option<String>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "to_commit_date", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Timestamp>)]
-    pub to_commit_date: Option<chrono::NaiveDateTime>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "from_id", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
-    pub from_id: Option<i32>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "from_last_name", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
-    pub from_last_name: Option<String>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "from_first_name", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
-    pub from_first_name: Option<String>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "from_start_date", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Timestamp>)]
-    pub from_start_date: Option<chrono::NaiveDateTime>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "from_commit", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
-    pub from_commit: Option<String>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "from_commit_date", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Timestamp>)]
-    pub from_commit_date: Option<chrono::NaiveDateTime>,
-    #[tabled(display_with = "display_option")]
-    #[diesel(column_name = "diff_type", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
-    pub diff_type: Option<String>,
+/// A row of the `dolt_column_diff` system table: one column of one table
+/// changed by a commit.
+#[derive(Debug, Clone, Queryable, Selectable)]
+#[diesel(table_name = dolt_column_diff)]
+pub struct DoltColumnDiff {
+    pub commit_hash: String,
+    pub table_name: String,
+    pub column_name: String,
+    pub committer: String,
+    pub email: String,
+    pub date: chrono::NaiveDateTime,
+    pub message: String,
+    pub diff_type: String,
 }
 
+/// A row of the `dolt_commits` system table: every commit in the
+/// database, whichever branch it is on.
+#[derive(Debug, Clone, Queryable, Selectable)]
+#[diesel(table_name = dolt_commits)]
+pub struct DoltCommit {
+    pub commit_hash: String,
+    pub committer: String,
+    pub email: String,
+    pub date: chrono::NaiveDateTime,
+    pub message: String,
+}
+
+/// A row of the `dolt_branches` system table.
 #[derive(Debug, Queryable, Selectable)]
 #[diesel(table_name = dolt_branches)]
 pub struct DoltBranch {
@@ -186,6 +212,7 @@ pub struct DoltBranch {
     pub branch: String,
 }
 
+/// A row of the `dolt_status` system table.
 #[derive(Debug, Queryable, Selectable)]
 #[diesel(table_name = dolt_status)]
 pub struct DoltStatus {
@@ -194,38 +221,177 @@ pub struct DoltStatus {
     pub status: String,
 }
 
-#[derive(Debug, Queryable, Selectable, Insertable, Identifiable)]
+/// A row of the `dolt_remotes` system table.
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = dolt_remotes)]
+pub struct DoltRemote {
+    pub name: String,
+    pub url: String,
+}
+
+/// A row of the `dolt_remote_branches` system table.
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = dolt_remote_branches)]
+pub struct DoltRemoteBranch {
+    pub name: String,
+    pub hash: String,
+    pub latest_committer: String,
+    pub latest_committer_email: String,
+    pub latest_commit_date: chrono::NaiveDateTime,
+    pub latest_commit_message: String,
+}
+
+/// A row of the `dolt_backups` system table.
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = dolt_backups)]
+pub struct DoltBackup {
+    pub name: String,
+    pub url: String,
+}
+
+/// A row of the `dolt_stashes` system table.
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = dolt_stashes)]
+pub struct DoltStash {
+    pub name: String,
+    pub stash_id: String,
+    pub branch: String,
+    pub hash: String,
+    pub commit_message: Option<String>,
+}
+
+/// A row of the `dolt_tags` system table.
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = dolt_tags)]
+pub struct DoltTag {
+    pub tag_name: String,
+    pub tag_hash: String,
+    pub tagger: String,
+    pub email: String,
+    pub date: chrono::NaiveDateTime,
+    pub message: String,
+}
+
+/// A row of `dolt_blame_<table>` for a table keyed by an integer `id`:
+/// the commit that last changed the row.
+#[derive(Debug, Clone, Queryable, QueryableByName)]
+pub struct DoltBlameEntry {
+    #[diesel(column_name = "id", sql_type = diesel::sql_types::Integer)]
+    pub id: i32,
+    #[diesel(column_name = "commit", sql_type = diesel::sql_types::Text)]
+    pub commit: String,
+    #[diesel(column_name = "commit_date", sql_type = diesel::sql_types::Timestamp)]
+    pub commit_date: chrono::NaiveDateTime,
+    #[diesel(column_name = "committer", sql_type = diesel::sql_types::Text)]
+    pub committer: String,
+    #[diesel(column_name = "email", sql_type = diesel::sql_types::Text)]
+    pub email: String,
+    #[diesel(column_name = "message", sql_type = diesel::sql_types::Text)]
+    pub message: String,
+}
+
+/// A row of the `dolt_conflicts` system table.
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = dolt_conflicts)]
+pub struct DoltTableConflicts {
+    pub table_name: String,
+    pub num_conflicts: u64,
+}
+
+/// A row of the `dolt_schema_conflicts` system table.
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = dolt_schema_conflicts)]
+pub struct DoltSchemaConflict {
+    pub table_name: String,
+    pub base_schema: String,
+    pub our_schema: String,
+    pub their_schema: String,
+    pub description: String,
+}
+
+/// A row of the `dolt_constraint_violations` system table.
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = dolt_constraint_violations)]
+pub struct DoltTableViolations {
+    pub table_name: String,
+    pub num_violations: u64,
+}
+
+/// The columns every `dolt_constraint_violations_<table>` has, besides the
+/// table's own.
+#[derive(Debug, QueryableByName)]
+pub struct DoltConstraintViolation {
+    #[diesel(column_name = "violation_type", sql_type = diesel::sql_types::Text)]
+    pub violation_type: String,
+    #[diesel(column_name = "violation_info", sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub violation_info: Option<String>,
+}
+
+/// The row returned by `CALL DOLT_VERIFY_CONSTRAINTS()`.
+#[derive(Debug, QueryableByName)]
+pub struct DoltVerifyConstraintsResponse {
+    #[diesel(column_name = "violations", sql_type = diesel::sql_types::Integer)]
+    pub violations: i32,
+}
+
+/// A row of `employees` before `start_date` was added.
+#[derive(Debug, PartialEq, Queryable, Selectable, Insertable, Identifiable, DoltDiff)]
 #[diesel(table_name = employees)]
+#[dolt_diff(name = DoltDiffEmployeesEntry)]
+#[cfg_attr(feature = "tabled", dolt_diff(tabled))]
 pub struct Employee {
     pub id: i32,
-    pub first_name: String,
     pub last_name: String,
+    pub first_name: String,
 }
 
-#[derive(Debug, Queryable, Selectable, Insertable, Identifiable)]
+/// A row of `employees` once `start_date` exists.
+#[derive(Debug, PartialEq, Queryable, Selectable, Insertable, Identifiable, DoltDiff)]
 #[diesel(table_name = employees)]
+#[dolt_diff(name = DoltDiffEmployeesEntryAfterEdit)]
+#[cfg_attr(feature = "tabled", dolt_diff(tabled))]
 pub struct EmployeeAfterEdit {
     pub id: i32,
-    pub first_name: Option<String>,
     pub last_name: Option<String>,
+    pub first_name: Option<String>,
     pub start_date: Option<chrono::NaiveDateTime>,
 }
 
-#[derive(Debug, Queryable, Selectable, Insertable)]
+/// A row of `teams`.
+#[derive(Debug, PartialEq, Queryable, Selectable, Insertable, DoltConflict)]
 #[diesel(table_name = teams)]
+#[cfg_attr(feature = "tabled", dolt_conflict(tabled))]
 pub struct Team {
     pub id: i32,
     pub name: String,
 }
 
-#[derive(Debug, Queryable)]
+/// A row of `employees_teams`.
+#[derive(Debug, PartialEq, Queryable, DoltDiff)]
 #[diesel(table_name = employees_teams)]
+#[dolt_diff(name = DoltDiffEmployeesTeamsEntry)]
+#[cfg_attr(feature = "tabled", dolt_diff(tabled))]
 pub struct EmployeeTeam {
     pub id: i32,
     pub employee_id: i32,
     pub team_id: i32,
 }
 
+// Lets the demo tables be read with `as_of`.
+crate::as_of_table!(employees {
+    id,
+    last_name,
+    first_name,
+    start_date
+});
+crate::as_of_table!(employees_teams {
+    id,
+    employee_id,
+    team_id
+});
+crate::as_of_table!(teams { id, name });
+
+/// An employee joined with their team.
 #[derive(Debug, PartialEq, Queryable, Selectable)]
 pub struct TeamEmployee {
     #[diesel(select_expression = teams::columns::name)]
@@ -239,6 +405,7 @@ pub struct TeamEmployee {
     pub last_name: Option<String>,
 }
 
+/// An employee joined with their team, including `start_date`.
 #[derive(Debug, PartialEq, Queryable, Selectable)]
 pub struct TeamEmployeeAfterEdit {
     #[diesel(select_expression = teams::columns::name)]
@@ -255,6 +422,7 @@ pub struct TeamEmployeeAfterEdit {
     pub start_date: Option<chrono::NaiveDateTime>,
 }
 
+/// An employee joined with their team, loaded through `sql_query`.
 #[derive(Debug, Queryable, QueryableByName)]
 pub struct MappedEmployeeTeamWithStartDate {
     #[diesel(column_name = "name", sql_type = diesel::sql_types::Text)]
@@ -265,6 +433,7 @@ pub struct MappedEmployeeTeamWithStartDate {
     pub employee_last_name: Option<String>,
 }
 
+/// A new `employees_teams` row, letting the database pick the id.
 #[derive(Debug, Insertable, AsChangeset)]
 #[diesel(table_name = employees_teams)]
 pub struct EmployeeTeamInsertable {
@@ -0,0 +1,287 @@
+//! SQL patches between revisions, from the `DOLT_PATCH()` table function.
+//!
+//! A patch moves changes between databases that share no remote: write it
+//! to a `.sql` file on one side, apply and commit it on the other.
+//!
+//! ```no_run
+//! # use dolt_dieselrs_getting_started::{patch::write_patch_file, DoltError, DoltRepo};
+//! # fn run(dev: &mut DoltRepo, staging: &mut DoltRepo) -> Result<(), DoltError> {
+//! write_patch_file(dev.connection(), "v1", "main", "/tmp/changes.sql")?;
+//! staging.apply_patch_file("/tmp/changes.sql", "Tim <tim@dolthub.com>", "Changes since v1")?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{fs, path::Path};
+
+use diesel::{
+    connection::SimpleConnection, mysql::Mysql, sql_query, sql_types::Text, Connection,
+    MysqlConnection, QueryDsl, RunQueryDsl,
+};
+
+use crate::{
+    as_of::Revision, custom_schema::dolt_status, error::DoltError, models::DoltPatchStatement,
+    repo::commit,
+};
+
+/// The statements that turn `from` into `to`, in the order they have to run.
+pub fn patch_statements(
+    conn: &mut MysqlConnection,
+    from: impl Into<Revision>,
+    to: impl Into<Revision>,
+) -> Result<Vec<DoltPatchStatement>, DoltError> {
+    let stmt = "SELECT statement_order, table_name, diff_type, statement \
+                FROM DOLT_PATCH(?, ?) ORDER BY statement_order";
+
+    Ok(sql_query(stmt)
+        .into_boxed::<Mysql>()
+        .bind::<Text, _>(from.into().to_arg())
+        .bind::<Text, _>(to.into().to_arg())
+        .load::<DoltPatchStatement>(conn)?)
+}
+
+/// Runs `statements` against the working set, all or nothing.
+pub fn apply_statements(
+    conn: &mut MysqlConnection,
+    statements: &[DoltPatchStatement],
+) -> Result<(), DoltError> {
+    conn.transaction::<_, DoltError, _>(|conn| {
+        for statement in statements {
+            // Patch statements carry their values inline, there's nothing to bind.
+            conn.batch_execute(&statement.statement)
+                .map_err(|e| DoltError::from_table_query(&statement.table_name, e))?;
+        }
+
+        Ok(())
+    })
+}
+
+/// Writes the patch from `from` to `to` to `path`, one statement per line,
+/// and returns the number of statements.
+pub fn write_patch_file(
+    conn: &mut MysqlConnection,
+    from: impl Into<Revision>,
+    to: impl Into<Revision>,
+    path: impl AsRef<Path>,
+) -> Result<usize, DoltError> {
+    let from = from.into();
+    let to = to.into();
+    let statements = patch_statements(conn, from.clone(), to.clone())?;
+
+    // Escaped, so a revision with a line break can't end the comment.
+    let mut sql = format!("-- DOLT_PATCH({:?}, {:?})\n", from.to_arg(), to.to_arg());
+    for statement in &statements {
+        sql.push_str(statement.statement.trim_end().trim_end_matches(';'));
+        sql.push_str(";\n");
+    }
+    fs::write(path, sql)?;
+
+    Ok(statements.len())
+}
+
+/// Reads the statements of a `.sql` file such as [`write_patch_file`] writes.
+pub fn read_patch_file(path: impl AsRef<Path>) -> Result<Vec<String>, DoltError> {
+    Ok(split_statements(&fs::read_to_string(path)?))
+}
+
+/// Runs the statements of the `.sql` file at `path` and commits them, all
+/// or nothing: if a statement or the commit fails, the working set is left
+/// as it was.
+///
+/// The commit stages every table, so this fails with
+/// [`DoltError::UncommittedChanges`] unless the working set is clean.
+pub fn apply_patch_file(
+    conn: &mut MysqlConnection,
+    path: impl AsRef<Path>,
+    author: &str,
+    message: &str,
+) -> Result<String, DoltError> {
+    let statements = read_patch_file(path)?;
+
+    conn.transaction::<_, DoltError, _>(|conn| {
+        ensure_clean(conn)?;
+
+        for statement in &statements {
+            conn.batch_execute(statement)
+                .map_err(|e| match statement_table(statement) {
+                    Some(table) => DoltError::from_table_query(table, e),
+                    None => DoltError::Query(e),
+                })?;
+        }
+
+        commit(conn, author, message)
+    })
+}
+
+/// Fails with [`DoltError::UncommittedChanges`] unless the working set is
+/// clean, for operations that end in a commit staging every table.
+pub(crate) fn ensure_clean(conn: &mut MysqlConnection) -> Result<(), DoltError> {
+    let changed = dolt_status::table
+        .select(dolt_status::table_name)
+        .load::<String>(conn)?;
+    if !changed.is_empty() {
+        return Err(DoltError::UncommittedChanges(changed.join(", ")));
+    }
+
+    Ok(())
+}
+
+/// The first backquoted name of a statement, which for the statements
+/// `DOLT_PATCH` writes is the table it acts on.
+fn statement_table(statement: &str) -> Option<&str> {
+    let start = statement.find('`')? + 1;
+    let end = start + statement[start..].find('`')?;
+
+    Some(&statement[start..end])
+}
+
+/// Splits a script into its statements, without the `;` that ends them.
+///
+/// Semicolons inside quotes, backticks and comments don't end a statement.
+/// Comments between statements are dropped.
+pub fn split_statements(sql: &str) -> Vec<String> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        match c {
+            '\'' | '"' | '`' => {
+                current.push(c);
+                i += 1;
+                while i < chars.len() {
+                    current.push(chars[i]);
+                    if chars[i] == '\\' && c != '`' {
+                        // The escaped character can't close the quote.
+                        if let Some(&escaped) = chars.get(i + 1) {
+                            current.push(escaped);
+                            i += 1;
+                        }
+                    } else if chars[i] == c {
+                        // A doubled quote just opens again on the next character.
+                        break;
+                    }
+                    i += 1;
+                }
+            }
+            // MySQL only treats `--` as a comment when whitespace follows.
+            '-' if next == Some('-')
+                && chars.get(i + 2).is_none_or(|after| after.is_whitespace()) =>
+            {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                current.push('\n');
+            }
+            '#' => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                current.push('\n');
+            }
+            '/' if next == Some('*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                // Leave i on the closing '/'.
+                i += 1;
+                current.push(' ');
+            }
+            ';' => {
+                let statement = current.trim();
+                if !statement.is_empty() {
+                    statements.push(statement.to_string());
+                }
+                current.clear();
+            }
+            _ => current.push(c),
+        }
+
+        i += 1;
+    }
+
+    let statement = current.trim();
+    if !statement.is_empty() {
+        statements.push(statement.to_string());
+    }
+
+    statements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_on_semicolons() {
+        assert_eq!(
+            split_statements("INSERT INTO `t` VALUES (1);\nDELETE FROM `t`;\n"),
+            ["INSERT INTO `t` VALUES (1)", "DELETE FROM `t`"]
+        );
+    }
+
+    #[test]
+    fn keeps_a_statement_without_trailing_semicolon() {
+        assert_eq!(
+            split_statements("DELETE FROM `t`; UPDATE `t` SET a = 1  \n"),
+            ["DELETE FROM `t`", "UPDATE `t` SET a = 1"]
+        );
+    }
+
+    #[test]
+    fn ignores_semicolons_in_quotes() {
+        assert_eq!(
+            split_statements("INSERT INTO `a;b` VALUES ('x;y', \"z;w\"); SELECT 1"),
+            ["INSERT INTO `a;b` VALUES ('x;y', \"z;w\")", "SELECT 1"]
+        );
+    }
+
+    #[test]
+    fn handles_backslash_escapes() {
+        assert_eq!(
+            split_statements(r"SELECT 'it\'s;'; SELECT 'a\\'; SELECT 2"),
+            [r"SELECT 'it\'s;'", r"SELECT 'a\\'", "SELECT 2"]
+        );
+    }
+
+    #[test]
+    fn handles_doubled_quotes() {
+        assert_eq!(
+            split_statements("SELECT 'it''s;'; SELECT `a``;b`; SELECT 3"),
+            ["SELECT 'it''s;'", "SELECT `a``;b`", "SELECT 3"]
+        );
+    }
+
+    #[test]
+    fn drops_comments() {
+        let sql = "-- DOLT_PATCH(\"a;\", \"b\")\n\
+                   # hash; comment\n\
+                   SELECT /* inline; */ 1;\n\
+                   /* block\n; comment */\n\
+                   SELECT 2; --\n";
+
+        assert_eq!(split_statements(sql), ["SELECT   1", "SELECT 2"]);
+    }
+
+    #[test]
+    fn double_dash_without_space_is_not_a_comment() {
+        assert_eq!(
+            split_statements("SELECT 1--x;SELECT 2"),
+            ["SELECT 1--x", "SELECT 2"]
+        );
+    }
+
+    #[test]
+    fn finds_the_table_of_a_statement() {
+        assert_eq!(
+            statement_table("UPDATE `employees` SET `last_name` = 'x'"),
+            Some("employees")
+        );
+        assert_eq!(statement_table("SELECT 1"), None);
+    }
+}
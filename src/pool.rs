@@ -0,0 +1,174 @@
+//! A connection pool that hands out connections bound to a branch.
+//!
+//! Every branch gets its own r2d2 pool, capped at `max_per_branch`
+//! connections, created the first time a connection for it is requested
+//! and closed with [`DoltPool::remove_branch`].
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, PoisonError},
+};
+
+use diesel::{
+    connection::{AnsiTransactionManager, SimpleConnection, TransactionManager},
+    r2d2::{ManageConnection, Pool, PooledConnection, R2D2Connection},
+    MysqlConnection,
+};
+
+use crate::{
+    connection::{database_name, establish_branch_connection, use_branch, verify_branch},
+    error::DoltError,
+};
+
+/// r2d2 manager for connections bound to a single branch.
+#[derive(Debug)]
+pub struct BranchConnectionManager {
+    database_url: String,
+    database: String,
+    branch: String,
+}
+
+impl BranchConnectionManager {
+    /// A manager for connections to `branch` of the database in `database_url`.
+    pub fn new(database_url: &str, branch: &str) -> Result<Self, DoltError> {
+        Ok(BranchConnectionManager {
+            database_url: database_url.to_string(),
+            database: database_name(database_url)?,
+            branch: branch.to_string(),
+        })
+    }
+
+    /// The branch every connection of this manager is bound to.
+    pub fn branch(&self) -> &str {
+        &self.branch
+    }
+
+    fn reset_session(&self, conn: &mut MysqlConnection) -> Result<(), DoltError> {
+        /*
+        Throw away whatever the borrower left behind: an open transaction
+        (and with it the transaction's working set) and a session that was
+        moved to another branch database.
+        */
+
+        let in_transaction = AnsiTransactionManager::transaction_manager_status_mut(conn)
+            .transaction_depth()?
+            .is_some();
+        if in_transaction {
+            AnsiTransactionManager::rollback_transaction(conn)?;
+        } else {
+            conn.batch_execute("ROLLBACK")?;
+        }
+
+        use_branch(conn, &self.database, &self.branch)
+    }
+}
+
+impl ManageConnection for BranchConnectionManager {
+    type Connection = MysqlConnection;
+    type Error = DoltError;
+
+    fn connect(&self) -> Result<MysqlConnection, DoltError> {
+        establish_branch_connection(&self.database_url, &self.branch)
+    }
+
+    fn is_valid(&self, conn: &mut MysqlConnection) -> Result<(), DoltError> {
+        // r2d2 runs this on every checkout from the pool.
+        verify_branch(conn, &self.branch)
+    }
+
+    fn has_broken(&self, conn: &mut MysqlConnection) -> bool {
+        // r2d2 runs this when a connection is returned, which makes it our only reset hook.
+        conn.is_broken() || self.reset_session(conn).is_err()
+    }
+}
+
+/// Branch-aware pool of Dolt connections.
+pub struct DoltPool {
+    database_url: String,
+    max_per_branch: u32,
+    pools: Mutex<HashMap<String, Pool<BranchConnectionManager>>>,
+}
+
+impl DoltPool {
+    /// A pool for the database in `database_url` with at most `max_per_branch`
+    /// open connections per branch, which can't be 0.
+    pub fn new(database_url: &str, max_per_branch: u32) -> Result<Self, DoltError> {
+        if max_per_branch == 0 {
+            return Err(DoltError::InvalidArgument(
+                "a branch pool needs room for at least one connection".to_string(),
+            ));
+        }
+
+        database_name(database_url)?;
+
+        Ok(DoltPool {
+            database_url: database_url.to_string(),
+            max_per_branch,
+            pools: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// A connection bound to `branch`, verified with `active_branch()`.
+    pub fn get(
+        &self,
+        branch: &str,
+    ) -> Result<PooledConnection<BranchConnectionManager>, DoltError> {
+        let pool = self.branch_pool(branch)?;
+        pool.get().map_err(DoltError::Pool)
+    }
+
+    /// Branches that currently have a pool.
+    pub fn branches(&self) -> Vec<String> {
+        let pools = self.pools.lock().unwrap_or_else(PoisonError::into_inner);
+        pools.keys().cloned().collect()
+    }
+
+    /// Open and idle connection counts for `branch`.
+    pub fn state(&self, branch: &str) -> Option<diesel::r2d2::State> {
+        let pools = self.pools.lock().unwrap_or_else(PoisonError::into_inner);
+        pools.get(branch).map(Pool::state)
+    }
+
+    /// Closes the pool for `branch`, for example once the branch is deleted.
+    /// Connections already handed out close when they are dropped.
+    ///
+    /// Returns whether there was a pool to close.
+    pub fn remove_branch(&self, branch: &str) -> bool {
+        let mut pools = self.pools.lock().unwrap_or_else(PoisonError::into_inner);
+        pools.remove(branch).is_some()
+    }
+
+    fn branch_pool(&self, branch: &str) -> Result<Pool<BranchConnectionManager>, DoltError> {
+        if let Some(pool) = self.existing_pool(branch) {
+            return Ok(pool);
+        }
+
+        /*
+        r2d2 retries failed connects until its timeout runs out, which turns
+        a typo in a branch name into a 30 second hang. Connect once ourselves
+        so a missing branch fails straight away with BranchNotFound. This
+        happens outside the lock, so a slow connect only holds up its own
+        branch.
+        */
+        establish_branch_connection(&self.database_url, branch)?;
+
+        let manager = BranchConnectionManager::new(&self.database_url, branch)?;
+        let mut pools = self.pools.lock().unwrap_or_else(PoisonError::into_inner);
+
+        // Another caller may have created the pool while we were connecting.
+        let pool = pools.entry(branch.to_string()).or_insert_with(|| {
+            Pool::builder()
+                .max_size(self.max_per_branch)
+                .min_idle(Some(0))
+                .test_on_check_out(true)
+                .build_unchecked(manager)
+        });
+
+        Ok(pool.clone())
+    }
+
+    fn existing_pool(&self, branch: &str) -> Option<Pool<BranchConnectionManager>> {
+        let pools = self.pools.lock().unwrap_or_else(PoisonError::into_inner);
+        pools.get(branch).cloned()
+    }
+}
@@ -0,0 +1,24 @@
+//! Terminal rendering of system table rows, behind the `tabled` feature.
+
+/// Prints rows as an indented table, one column per struct field.
+pub fn print_as_table<T: tabled::Tabled>(table_entries: Vec<T>) {
+    // I am using tabled here because dolt_diff_<table> is a wide table
+    let results_table = tabled::tables::ExtendedTable::new(table_entries)
+        .to_string()
+        .replace('\n', "\n\t");
+
+    println!("\t{results_table}");
+}
+
+/// Prints rows whose columns are only known at runtime as an indented table.
+pub fn print_records(headers: Vec<String>, records: Vec<Vec<String>>) {
+    let mut builder = tabled::builder::Builder::new();
+    builder.set_header(headers);
+    for record in records {
+        builder.push_record(record);
+    }
+
+    let results_table = builder.build().to_string().replace('\n', "\n\t");
+
+    println!("\t{results_table}");
+}
@@ -0,0 +1,114 @@
+use diesel::{
+    mysql::Mysql, sql_query, sql_types::Text, MysqlConnection, QueryableByName, RunQueryDsl,
+};
+
+use crate::error::DoltError;
+
+/// Builds a `CALL DOLT_*(?, ...)` statement with every argument bound.
+///
+/// ```no_run
+/// # use dolt_dieselrs_getting_started::{models::DoltCommitResponse, DoltProcedure};
+/// # fn run(conn: &mut diesel::MysqlConnection) -> Result<(), dolt_dieselrs_getting_started::DoltError> {
+/// let commits = DoltProcedure::new("DOLT_COMMIT")
+///     .args(["-m", "Tim's first commit"])
+///     .load::<DoltCommitResponse>(conn)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct DoltProcedure {
+    name: &'static str,
+    args: Vec<String>,
+    subject: Option<String>,
+}
+
+impl DoltProcedure {
+    /// Starts a call to `name`, which must look like `DOLT_SOMETHING`.
+    pub fn new(name: &'static str) -> Self {
+        DoltProcedure {
+            name,
+            args: Vec::new(),
+            subject: None,
+        }
+    }
+
+    /// Names what the call acts on in its errors, when that isn't the last
+    /// argument, e.g. the remote of `DOLT_REMOTE('add', name, url)`.
+    pub fn subject<S: Into<String>>(mut self, subject: S) -> Self {
+        self.subject = Some(subject.into());
+        self
+    }
+
+    /// Appends a single argument.
+    pub fn arg<S: Into<String>>(mut self, arg: S) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Appends several arguments in order.
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// The statement sent to the server, with `?` for every argument.
+    pub fn to_sql(&self) -> String {
+        // Only the procedure name ends up in the statement, every argument is a placeholder.
+        let placeholders = vec!["?"; self.args.len()].join(", ");
+        format!("CALL {}({placeholders})", self.name)
+    }
+
+    /// Runs the procedure and loads the rows it returns.
+    pub fn load<T>(self, conn: &mut MysqlConnection) -> Result<Vec<T>, DoltError>
+    where
+        T: QueryableByName<Mysql> + 'static,
+    {
+        /*
+        The procedure name can't be bound, so make sure it is one of Dolt's
+        and not something that sneaks more SQL into the statement.
+        */
+
+        let valid_name = self.name.starts_with("DOLT_")
+            && self
+                .name
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c == '_');
+
+        if !valid_name {
+            return Err(DoltError::Procedure {
+                procedure: self.name.to_string(),
+                source: diesel::result::Error::QueryBuilderError(
+                    format!("Not a Dolt procedure: {}", self.name).into(),
+                ),
+            });
+        }
+
+        // The last non-flag argument is usually the branch, table or commit we act on.
+        let subject = self.subject.clone().unwrap_or_else(|| {
+            self.args
+                .iter()
+                .rev()
+                .find(|arg| !arg.starts_with('-'))
+                .cloned()
+                .unwrap_or_default()
+        });
+
+        let mut query = sql_query(self.to_sql()).into_boxed::<Mysql>();
+        for arg in self.args {
+            query = query.bind::<Text, _>(arg);
+        }
+
+        query
+            .load::<T>(conn)
+            .map_err(|e| DoltError::from_procedure(self.name, &subject, e))
+    }
+}
+
+/// Quotes a table or database name for use in a statement.
+pub fn quote_identifier(name: &str) -> String {
+    // Table and database names can't be bound, so quote them the MySQL way instead.
+    format!("`{}`", name.replace('`', "``"))
+}
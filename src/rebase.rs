@@ -0,0 +1,222 @@
+//! Interactive rebases through the `dolt_rebase` plan table.
+//!
+//! `DOLT_REBASE('-i', upstream)` writes one row per commit to `dolt_rebase`.
+//! The plan can be edited (actions, messages, order) before the rebase is
+//! continued or aborted:
+//!
+//! ```no_run
+//! # use dolt_dieselrs_getting_started::{rebase::RebaseAction, DoltError, DoltRepo};
+//! # fn run(repo: &mut DoltRepo) -> Result<(), DoltError> {
+//! let mut plan = repo.start_rebase("main")?;
+//! for step in plan.iter_mut().skip(1) {
+//!     step.action = RebaseAction::Fixup;
+//!     repo.update_rebase_step(step)?;
+//! }
+//! repo.continue_rebase()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{fmt, str::FromStr};
+
+use diesel::{
+    connection::SimpleConnection,
+    sql_query,
+    sql_types::{Double, Text},
+    MysqlConnection, QueryableByName, RunQueryDsl,
+};
+
+use crate::{
+    connection::use_branch,
+    error::DoltError,
+    models::DoltCallResponse,
+    procedure::{quote_identifier, DoltProcedure},
+};
+
+/// What to do with a commit of the plan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RebaseAction {
+    Pick,
+    Drop,
+    Reword,
+    Squash,
+    Fixup,
+}
+
+impl RebaseAction {
+    /// The action as Dolt spells it in `dolt_rebase`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RebaseAction::Pick => "pick",
+            RebaseAction::Drop => "drop",
+            RebaseAction::Reword => "reword",
+            RebaseAction::Squash => "squash",
+            RebaseAction::Fixup => "fixup",
+        }
+    }
+}
+
+impl fmt::Display for RebaseAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RebaseAction {
+    type Err = String;
+
+    fn from_str(action: &str) -> Result<Self, Self::Err> {
+        match action {
+            "pick" => Ok(RebaseAction::Pick),
+            "drop" => Ok(RebaseAction::Drop),
+            "reword" => Ok(RebaseAction::Reword),
+            "squash" => Ok(RebaseAction::Squash),
+            "fixup" => Ok(RebaseAction::Fixup),
+            _ => Err(format!("Unknown rebase action: {action}")),
+        }
+    }
+}
+
+/// A row of the `dolt_rebase` plan.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RebaseStep {
+    /// Steps run from the lowest order up; fractions put a step in between.
+    pub rebase_order: f64,
+    pub action: RebaseAction,
+    pub commit_hash: String,
+    pub commit_message: String,
+}
+
+#[derive(QueryableByName)]
+struct RebasePlanRow {
+    #[diesel(sql_type = Double)]
+    rebase_order: f64,
+    #[diesel(sql_type = Text)]
+    action: String,
+    #[diesel(sql_type = Text)]
+    commit_hash: String,
+    #[diesel(sql_type = Text)]
+    commit_message: String,
+}
+
+/// Starts an interactive rebase of `branch` onto `upstream` and returns the plan.
+pub fn start_rebase(
+    conn: &mut MysqlConnection,
+    database: &str,
+    branch: &str,
+    upstream: &str,
+) -> Result<Vec<RebaseStep>, DoltError> {
+    /*
+    The rebase runs on a dolt_rebase_<branch> branch it checks out itself,
+    which a session pinned to the `database/branch` revision database can't
+    do. Move the session to the plain database with the branch checked out
+    for as long as the rebase runs.
+    */
+
+    conn.batch_execute(&format!("USE {}", quote_identifier(database)))?;
+
+    let started = DoltProcedure::new("DOLT_CHECKOUT")
+        .arg(branch)
+        .load::<DoltCallResponse>(conn)
+        .and_then(|_| {
+            DoltProcedure::new("DOLT_REBASE")
+                .args(["-i", upstream])
+                .load::<DoltCallResponse>(conn)
+        });
+    if let Err(e) = started {
+        // Whichever call failed, the session must not stay on the plain database.
+        use_branch(conn, database, branch)?;
+        return Err(e);
+    }
+
+    rebase_plan(conn).or_else(|e| {
+        // A rebase whose plan can't be read can't be edited either, so drop it.
+        let _ = DoltProcedure::new("DOLT_REBASE")
+            .arg("--abort")
+            .load::<DoltCallResponse>(conn);
+        use_branch(conn, database, branch)?;
+        Err(e)
+    })
+}
+
+/// The plan of the rebase in progress, in the order it will run.
+pub fn rebase_plan(conn: &mut MysqlConnection) -> Result<Vec<RebaseStep>, DoltError> {
+    // rebase_order is a DECIMAL, which Diesel can only read with the bigdecimal feature.
+    let rows = sql_query(
+        "SELECT CAST(rebase_order AS DOUBLE) AS rebase_order, action, commit_hash, commit_message \
+         FROM dolt_rebase ORDER BY rebase_order",
+    )
+    .load::<RebasePlanRow>(conn)
+    .map_err(|e| DoltError::from_table_query("dolt_rebase", e))?;
+
+    rows.into_iter()
+        .map(|row| {
+            let action = row.action.parse::<RebaseAction>().map_err(|e| {
+                DoltError::Query(diesel::result::Error::DeserializationError(e.into()))
+            })?;
+
+            Ok(RebaseStep {
+                rebase_order: row.rebase_order,
+                action,
+                commit_hash: row.commit_hash,
+                commit_message: row.commit_message,
+            })
+        })
+        .collect()
+}
+
+/// Writes the action, message and order of `step` back to the plan.
+pub fn update_rebase_step(conn: &mut MysqlConnection, step: &RebaseStep) -> Result<(), DoltError> {
+    sql_query(
+        "UPDATE dolt_rebase SET rebase_order = ?, action = ?, commit_message = ? \
+         WHERE commit_hash = ?",
+    )
+    .bind::<Double, _>(step.rebase_order)
+    .bind::<Text, _>(step.action.as_str())
+    .bind::<Text, _>(&step.commit_message)
+    .bind::<Text, _>(&step.commit_hash)
+    .execute(conn)
+    .map_err(|e| DoltError::from_table_query("dolt_rebase", e))?;
+
+    Ok(())
+}
+
+/// Runs the plan and binds the session to `branch` again, even if the rebase
+/// stops; a stopped rebase can still be aborted.
+pub fn continue_rebase(
+    conn: &mut MysqlConnection,
+    database: &str,
+    branch: &str,
+) -> Result<(), DoltError> {
+    finish_rebase(conn, database, branch, "--continue")
+}
+
+/// Abandons the rebase and binds the session to `branch` again.
+pub fn abort_rebase(
+    conn: &mut MysqlConnection,
+    database: &str,
+    branch: &str,
+) -> Result<(), DoltError> {
+    finish_rebase(conn, database, branch, "--abort")
+}
+
+fn finish_rebase(
+    conn: &mut MysqlConnection,
+    database: &str,
+    branch: &str,
+    flag: &str,
+) -> Result<(), DoltError> {
+    // A failed continue rebinds too, so go back to the rebase branch first.
+    conn.batch_execute(&format!("USE {}", quote_identifier(database)))?;
+    let finished = DoltProcedure::new("DOLT_CHECKOUT")
+        .arg(format!("dolt_rebase_{branch}"))
+        .load::<DoltCallResponse>(conn)
+        .and_then(|_| {
+            DoltProcedure::new("DOLT_REBASE")
+                .arg(flag)
+                .load::<DoltCallResponse>(conn)
+        });
+    // Rebind either way so a failed rebase doesn't leave the session off `branch`.
+    use_branch(conn, database, branch)?;
+    finished.map(|_| ())
+}
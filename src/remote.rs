@@ -0,0 +1,183 @@
+//! Remotes, and fetching, pulling, pushing and cloning through them.
+//!
+//! Any remote URL Dolt understands works, including `file://` directories,
+//! which let two databases on the same machine sync without a hosted
+//! service. Paths are resolved by the sql-server, not by this process.
+//!
+//! ```no_run
+//! # use dolt_dieselrs_getting_started::{remote::file_remote_url, DoltError, DoltRepo};
+//! # fn run(repo: &mut DoltRepo) -> Result<(), DoltError> {
+//! let url = file_remote_url("/tmp/dieselrs_big_demo_remote").unwrap();
+//! repo.add_remote("origin", &url)?;
+//! repo.push("origin", "main", false)?;
+//! let mut clone = repo.clone_database(&url, "dieselrs_big_demo_clone")?;
+//! clone.pull("origin", Some("main"))?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::path::Path;
+
+use diesel::{ExpressionMethods, MysqlConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+
+use crate::{
+    conflicts::{allow_commit_conflicts, allowing_commit_conflicts, merge_report, PullOutcome},
+    custom_schema::{dolt_remote_branches, dolt_remotes},
+    error::DoltError,
+    models::{DoltCallResponse, DoltPullResponse, DoltRemote, DoltRemoteBranch},
+    procedure::DoltProcedure,
+};
+
+/// A `file://` remote URL for the directory `path`, `None` unless it's absolute.
+pub fn file_remote_url(path: impl AsRef<Path>) -> Option<String> {
+    url::Url::from_directory_path(path)
+        .ok()
+        .map(|url| url.to_string().trim_end_matches('/').to_string())
+}
+
+/// Lists all remotes.
+pub fn remotes(conn: &mut MysqlConnection) -> Result<Vec<DoltRemote>, DoltError> {
+    Ok(dolt_remotes::table
+        .select(DoltRemote::as_select())
+        .order(dolt_remotes::name.asc())
+        .load::<DoltRemote>(conn)?)
+}
+
+/// Whether a remote named `remote` exists.
+pub fn remote_exists(conn: &mut MysqlConnection, remote: &str) -> Result<bool, DoltError> {
+    let results = dolt_remotes::table
+        .select(dolt_remotes::name)
+        .filter(dolt_remotes::name.eq(remote))
+        .load::<String>(conn)?;
+
+    Ok(!results.is_empty())
+}
+
+/// Lists the branches fetched from all remotes, like `origin/main`.
+pub fn remote_branches(conn: &mut MysqlConnection) -> Result<Vec<DoltRemoteBranch>, DoltError> {
+    Ok(dolt_remote_branches::table
+        .select(DoltRemoteBranch::as_select())
+        .order(dolt_remote_branches::name.asc())
+        .load::<DoltRemoteBranch>(conn)?)
+}
+
+/// Adds a remote named `remote` pointing at `url`.
+pub fn add_remote(conn: &mut MysqlConnection, remote: &str, url: &str) -> Result<(), DoltError> {
+    if remote_exists(conn, remote)? {
+        return Err(DoltError::RemoteExists(remote.to_string()));
+    }
+
+    DoltProcedure::new("DOLT_REMOTE")
+        .args(["add", remote, url])
+        .subject(remote)
+        .load::<DoltCallResponse>(conn)?;
+
+    Ok(())
+}
+
+/// Removes the remote named `remote` and its fetched branches.
+pub fn remove_remote(conn: &mut MysqlConnection, remote: &str) -> Result<(), DoltError> {
+    if !remote_exists(conn, remote)? {
+        return Err(DoltError::RemoteNotFound(remote.to_string()));
+    }
+
+    DoltProcedure::new("DOLT_REMOTE")
+        .args(["remove", remote])
+        .load::<DoltCallResponse>(conn)?;
+
+    Ok(())
+}
+
+/// Fetches `branch` (or every branch) from `remote`.
+pub fn fetch(
+    conn: &mut MysqlConnection,
+    remote: &str,
+    branch: Option<&str>,
+) -> Result<(), DoltError> {
+    if !remote_exists(conn, remote)? {
+        return Err(DoltError::RemoteNotFound(remote.to_string()));
+    }
+
+    DoltProcedure::new("DOLT_FETCH")
+        .arg(remote)
+        .args(branch)
+        .load::<DoltCallResponse>(conn)?;
+
+    Ok(())
+}
+
+/// Fetches from `remote` and merges `branch` (or the tracked branch) into
+/// the current branch.
+///
+/// Like [`DoltRepo::merge_keeping_conflicts`](crate::DoltRepo::merge_keeping_conflicts),
+/// conflicts stay in the working set and are listed in the outcome's report.
+pub fn pull(
+    conn: &mut MysqlConnection,
+    remote: &str,
+    branch: Option<&str>,
+) -> Result<PullOutcome, DoltError> {
+    if !remote_exists(conn, remote)? {
+        return Err(DoltError::RemoteNotFound(remote.to_string()));
+    }
+
+    let (pull, report) = allowing_commit_conflicts(conn, |conn| {
+        let result = DoltProcedure::new("DOLT_PULL")
+            .arg(remote)
+            .args(branch)
+            .load::<DoltPullResponse>(conn)?;
+
+        let pull = result
+            .into_iter()
+            .next()
+            .ok_or_else(|| DoltError::Procedure {
+                procedure: "DOLT_PULL".to_string(),
+                source: diesel::result::Error::NotFound,
+            })?;
+
+        Ok((pull, merge_report(conn)?))
+    })?;
+
+    if report.is_clean() {
+        allow_commit_conflicts(conn, false)?;
+    }
+
+    Ok(PullOutcome { pull, report })
+}
+
+/// Pushes `branch` to `remote`, overwriting its history when `force` is set.
+pub fn push(
+    conn: &mut MysqlConnection,
+    remote: &str,
+    branch: &str,
+    force: bool,
+) -> Result<(), DoltError> {
+    if !remote_exists(conn, remote)? {
+        return Err(DoltError::RemoteNotFound(remote.to_string()));
+    }
+
+    let mut procedure = DoltProcedure::new("DOLT_PUSH");
+    if force {
+        procedure = procedure.arg("--force");
+    }
+
+    procedure
+        .args([remote, branch])
+        .load::<DoltCallResponse>(conn)?;
+
+    Ok(())
+}
+
+/// Clones `url` into a new database named `database` on the same server.
+///
+/// The clone gets `url` as its `origin` remote.
+pub fn clone_database(
+    conn: &mut MysqlConnection,
+    url: &str,
+    database: &str,
+) -> Result<(), DoltError> {
+    DoltProcedure::new("DOLT_CLONE")
+        .args([url, database])
+        .load::<DoltCallResponse>(conn)?;
+
+    Ok(())
+}
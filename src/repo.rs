@@ -0,0 +1,906 @@
+use std::{env, path::Path};
+
+use diesel::{
+    mysql::Mysql, result::ConnectionError, sql_query, Connection, ExpressionMethods,
+    MysqlConnection, QueryDsl, QueryableByName, RunQueryDsl, SelectableHelper,
+};
+
+use crate::{
+    as_of::Revision,
+    backup::{add_backup, backups, remove_backup, restore_backup, sync_backup},
+    blame::{blame, column_blame, ColumnBlame, ColumnValues},
+    column_diff::{column_changes, column_history, ColumnChanges, ColumnDiffFilter},
+    conflicts::{
+        allow_commit_conflicts, allowing_commit_conflicts, conflicts, merge_report,
+        resolve_conflicts, resolve_conflicts_with, table_conflicts, verify_constraints,
+        CherryPickOutcome, ConflictRow, ConflictSide, MergeOutcome, MergeReport, PullOutcome,
+        RevertOutcome, ViolationGroup,
+    },
+    connection::{database_name, use_branch, verify_branch, with_database},
+    custom_schema::{
+        dolt_branches, dolt_commit_ancestors, dolt_log, dolt_stashes, dolt_status, dolt_tags,
+    },
+    diff::{diff_between, diff_from_merge_base, diff_stat, diff_summary},
+    error::DoltError,
+    graph::{ahead_behind, hash_of, is_ancestor, merge_base, CommitGraph},
+    history::{history_of, DoltHistory, HistoryEntry},
+    models::{
+        ActiveBranch, DoltBackup, DoltBranch, DoltCallResponse, DoltCherryPickResponse,
+        DoltColumnDiff, DoltCommitResponse, DoltDiffStat, DoltDiffSummary, DoltLogEntry,
+        DoltMergeResponse, DoltRemote, DoltRemoteBranch, DoltSchemaDiff, DoltStash, DoltStatus,
+        DoltTableConflicts, DoltTag,
+    },
+    patch::{apply_patch_file, apply_statements, ensure_clean, patch_statements, write_patch_file},
+    procedure::{quote_identifier, DoltProcedure},
+    rebase::{
+        abort_rebase, continue_rebase, rebase_plan, start_rebase, update_rebase_step, RebaseStep,
+    },
+    remote::{
+        add_remote, clone_database, fetch, pull, push, remote_branches, remotes, remove_remote,
+    },
+    schema_diff::schema_diff,
+};
+
+/// A connection to a Dolt database that is bound to one branch.
+pub struct DoltRepo {
+    conn: MysqlConnection,
+    database_url: String,
+    database: String,
+    branch: String,
+}
+
+impl DoltRepo {
+    /// Connects to `database_url` and binds to the database's default branch.
+    pub fn connect(database_url: &str) -> Result<Self, DoltError> {
+        let mut conn = MysqlConnection::establish(database_url)?;
+        let branch = active_branch(&mut conn)?;
+
+        DoltRepo::bind(conn, database_url, &branch)
+    }
+
+    /// Connects to `database_url` bound to `branch`.
+    pub fn open(database_url: &str, branch: &str) -> Result<Self, DoltError> {
+        let conn = MysqlConnection::establish(database_url)?;
+
+        DoltRepo::bind(conn, database_url, branch)
+    }
+
+    fn bind(
+        mut conn: MysqlConnection,
+        database_url: &str,
+        branch: &str,
+    ) -> Result<Self, DoltError> {
+        let database = database_name(database_url)?;
+        use_branch(&mut conn, &database, branch)?;
+
+        Ok(DoltRepo {
+            conn,
+            database_url: database_url.to_string(),
+            database,
+            branch: branch.to_string(),
+        })
+    }
+
+    /// Connects to the database in the `DATABASE_URL` environment variable.
+    pub fn from_env() -> Result<Self, DoltError> {
+        let database_url = env::var("DATABASE_URL").map_err(|_| {
+            ConnectionError::InvalidConnectionUrl("DATABASE_URL must be set".to_string())
+        })?;
+
+        DoltRepo::connect(&database_url)
+    }
+
+    /// The URL this repo connected to.
+    pub fn database_url(&self) -> &str {
+        &self.database_url
+    }
+
+    /// The database name, without any branch qualifier.
+    pub fn database(&self) -> &str {
+        &self.database
+    }
+
+    /// The branch this repo has checked out.
+    pub fn branch(&self) -> &str {
+        &self.branch
+    }
+
+    /// Fails unless the session is still on [`DoltRepo::branch`].
+    pub fn verify_branch(&mut self) -> Result<(), DoltError> {
+        verify_branch(&mut self.conn, &self.branch)
+    }
+
+    /// The underlying connection, for regular Diesel queries on the current branch.
+    pub fn connection(&mut self) -> &mut MysqlConnection {
+        &mut self.conn
+    }
+
+    /// Asks the server for the session's active branch.
+    pub fn active_branch(&mut self) -> Result<String, DoltError> {
+        active_branch(&mut self.conn)
+    }
+
+    /// Stages every table and commits, returning the new commit hash.
+    pub fn commit(&mut self, author: &str, message: &str) -> Result<String, DoltError> {
+        commit(&mut self.conn, author, message)
+    }
+
+    /// Lists all branches.
+    pub fn branches(&mut self) -> Result<Vec<DoltBranch>, DoltError> {
+        Ok(dolt_branches::table
+            .select(DoltBranch::as_select())
+            .order(dolt_branches::name.asc())
+            .load::<DoltBranch>(&mut self.conn)?)
+    }
+
+    /// Whether a branch named `branch` exists.
+    pub fn branch_exists(&mut self, branch: &str) -> Result<bool, DoltError> {
+        let results = dolt_branches::table
+            .select(dolt_branches::name)
+            .filter(dolt_branches::name.eq(branch))
+            .load::<String>(&mut self.conn)?;
+
+        Ok(!results.is_empty())
+    }
+
+    /// Creates `branch` at the current HEAD without checking it out.
+    pub fn create_branch(&mut self, branch: &str) -> Result<(), DoltError> {
+        if self.branch_exists(branch)? {
+            return Err(DoltError::BranchExists(branch.to_string()));
+        }
+
+        DoltProcedure::new("DOLT_BRANCH")
+            .arg(branch)
+            .load::<DoltCallResponse>(&mut self.conn)?;
+
+        Ok(())
+    }
+
+    /// Deletes `branch`, even with unmerged changes when `force` is set.
+    pub fn delete_branch(&mut self, branch: &str, force: bool) -> Result<(), DoltError> {
+        // '-D' deletes even if the branch has unmerged changes.
+        let flag = if force { "-D" } else { "-d" };
+
+        DoltProcedure::new("DOLT_BRANCH")
+            .args([flag, branch])
+            .load::<DoltCallResponse>(&mut self.conn)?;
+
+        Ok(())
+    }
+
+    /// Lists all tags, oldest first.
+    pub fn tags(&mut self) -> Result<Vec<DoltTag>, DoltError> {
+        Ok(dolt_tags::table
+            .select(DoltTag::as_select())
+            .order(dolt_tags::date.asc())
+            .load::<DoltTag>(&mut self.conn)?)
+    }
+
+    /// Whether a tag named `tag` exists.
+    pub fn tag_exists(&mut self, tag: &str) -> Result<bool, DoltError> {
+        let results = dolt_tags::table
+            .select(dolt_tags::tag_name)
+            .filter(dolt_tags::tag_name.eq(tag))
+            .load::<String>(&mut self.conn)?;
+
+        Ok(!results.is_empty())
+    }
+
+    /// Tags `revision`, with an annotation when `message` is given.
+    pub fn create_tag(
+        &mut self,
+        tag: &str,
+        revision: impl Into<Revision>,
+        message: Option<&str>,
+    ) -> Result<(), DoltError> {
+        if self.tag_exists(tag)? {
+            return Err(DoltError::TagExists(tag.to_string()));
+        }
+
+        let mut procedure = DoltProcedure::new("DOLT_TAG");
+        if let Some(message) = message {
+            procedure = procedure.args(["-m", message]);
+        }
+
+        procedure
+            .args([tag.to_string(), revision.into().to_arg()])
+            .subject(tag)
+            .load::<DoltCallResponse>(&mut self.conn)?;
+
+        Ok(())
+    }
+
+    /// Deletes `tag`.
+    pub fn delete_tag(&mut self, tag: &str) -> Result<(), DoltError> {
+        if !self.tag_exists(tag)? {
+            return Err(DoltError::TagNotFound(tag.to_string()));
+        }
+
+        DoltProcedure::new("DOLT_TAG")
+            .args(["-d", tag])
+            .load::<DoltCallResponse>(&mut self.conn)?;
+
+        Ok(())
+    }
+
+    /// Creates `branch` at `tag` and checks it out, like `git checkout -b branch tag`.
+    ///
+    /// Tags can't be committed to, so working from one needs a branch.
+    pub fn checkout_tag(&mut self, tag: &str, branch: &str) -> Result<(), DoltError> {
+        if !self.tag_exists(tag)? {
+            return Err(DoltError::TagNotFound(tag.to_string()));
+        }
+        if self.branch_exists(branch)? {
+            return Err(DoltError::BranchExists(branch.to_string()));
+        }
+
+        DoltProcedure::new("DOLT_BRANCH")
+            .args([branch, tag])
+            .subject(branch)
+            .load::<DoltCallResponse>(&mut self.conn)?;
+
+        self.checkout(branch)
+    }
+
+    /// Rebinds this repo's connection to `branch`.
+    ///
+    /// Unlike `CALL DOLT_CHECKOUT` this only affects this connection; other
+    /// repos stay on their own branches.
+    pub fn checkout(&mut self, branch: &str) -> Result<(), DoltError> {
+        use_branch(&mut self.conn, &self.database, branch)?;
+
+        self.branch = branch.to_string();
+        Ok(())
+    }
+
+    /// Parks the working set on the stash named `stash`, leaving it clean.
+    pub fn stash(&mut self, stash: &str) -> Result<(), DoltError> {
+        DoltProcedure::new("DOLT_STASH")
+            .args(["push", stash])
+            .load::<DoltCallResponse>(&mut self.conn)?;
+
+        Ok(())
+    }
+
+    /// Applies the latest entry of `stash` to the working set and drops it.
+    pub fn stash_pop(&mut self, stash: &str) -> Result<(), DoltError> {
+        DoltProcedure::new("DOLT_STASH")
+            .args(["pop", stash])
+            .load::<DoltCallResponse>(&mut self.conn)?;
+
+        Ok(())
+    }
+
+    /// Throws away the latest entry of `stash`.
+    pub fn stash_drop(&mut self, stash: &str) -> Result<(), DoltError> {
+        DoltProcedure::new("DOLT_STASH")
+            .args(["drop", stash])
+            .load::<DoltCallResponse>(&mut self.conn)?;
+
+        Ok(())
+    }
+
+    /// Throws away every entry of `stash`.
+    pub fn stash_clear(&mut self, stash: &str) -> Result<(), DoltError> {
+        DoltProcedure::new("DOLT_STASH")
+            .args(["clear", stash])
+            .load::<DoltCallResponse>(&mut self.conn)?;
+
+        Ok(())
+    }
+
+    /// Lists the stashed working sets.
+    pub fn stashes(&mut self) -> Result<Vec<DoltStash>, DoltError> {
+        Ok(dolt_stashes::table
+            .select(DoltStash::as_select())
+            .load::<DoltStash>(&mut self.conn)?)
+    }
+
+    /// Checks out `branch`, taking uncommitted changes along.
+    ///
+    /// The working set is stashed, the branch checked out and the stash
+    /// popped there. If popping fails the changes stay on the stash.
+    pub fn checkout_with_autostash(&mut self, branch: &str) -> Result<(), DoltError> {
+        const AUTOSTASH: &str = "autostash";
+
+        if self.status()?.is_empty() {
+            return self.checkout(branch);
+        }
+
+        self.stash(AUTOSTASH)?;
+        if let Err(e) = self.checkout(branch) {
+            // Put everything back where it was before giving up.
+            self.stash_pop(AUTOSTASH)?;
+            return Err(e);
+        }
+
+        self.stash_pop(AUTOSTASH)
+    }
+
+    /// Lists the configured remotes.
+    pub fn remotes(&mut self) -> Result<Vec<DoltRemote>, DoltError> {
+        remotes(&mut self.conn)
+    }
+
+    /// Lists the branches fetched from remotes.
+    pub fn remote_branches(&mut self) -> Result<Vec<DoltRemoteBranch>, DoltError> {
+        remote_branches(&mut self.conn)
+    }
+
+    /// Adds a remote named `remote` pointing at `url`.
+    pub fn add_remote(&mut self, remote: &str, url: &str) -> Result<(), DoltError> {
+        add_remote(&mut self.conn, remote, url)
+    }
+
+    /// Removes the remote named `remote`.
+    pub fn remove_remote(&mut self, remote: &str) -> Result<(), DoltError> {
+        remove_remote(&mut self.conn, remote)
+    }
+
+    /// Fetches `branch` (or every branch) from `remote`.
+    pub fn fetch(&mut self, remote: &str, branch: Option<&str>) -> Result<(), DoltError> {
+        fetch(&mut self.conn, remote, branch)
+    }
+
+    /// Fetches from `remote` and merges `branch` into the current branch,
+    /// keeping any conflicts like [`DoltRepo::merge_keeping_conflicts`].
+    pub fn pull(&mut self, remote: &str, branch: Option<&str>) -> Result<PullOutcome, DoltError> {
+        pull(&mut self.conn, remote, branch)
+    }
+
+    /// Pushes `branch` to `remote`.
+    pub fn push(&mut self, remote: &str, branch: &str, force: bool) -> Result<(), DoltError> {
+        push(&mut self.conn, remote, branch, force)
+    }
+
+    /// Clones `url` into a new `database` on this server and connects to it.
+    pub fn clone_database(&mut self, url: &str, database: &str) -> Result<DoltRepo, DoltError> {
+        clone_database(&mut self.conn, url, database)?;
+
+        DoltRepo::connect(&with_database(&self.database_url, database)?)
+    }
+
+    /// Lists the configured backups.
+    pub fn backups(&mut self) -> Result<Vec<DoltBackup>, DoltError> {
+        backups(&mut self.conn)
+    }
+
+    /// Adds a backup named `backup` writing to `url`.
+    pub fn add_backup(&mut self, backup: &str, url: &str) -> Result<(), DoltError> {
+        add_backup(&mut self.conn, backup, url)
+    }
+
+    /// Removes the backup named `backup`.
+    pub fn remove_backup(&mut self, backup: &str) -> Result<(), DoltError> {
+        remove_backup(&mut self.conn, backup)
+    }
+
+    /// Copies every branch, tag and working set of this database to `backup`.
+    pub fn sync_backup(&mut self, backup: &str) -> Result<(), DoltError> {
+        sync_backup(&mut self.conn, backup)
+    }
+
+    /// Restores the backup at `url` as `database` on this server and
+    /// connects to it. `force` replaces an existing database of that name.
+    pub fn restore_backup(
+        &mut self,
+        url: &str,
+        database: &str,
+        force: bool,
+    ) -> Result<DoltRepo, DoltError> {
+        restore_backup(&mut self.conn, url, database, force)?;
+
+        DoltRepo::connect(&with_database(&self.database_url, database)?)
+    }
+
+    /// Merges `branch` into the current branch.
+    ///
+    /// A merge with conflicts or schema conflicts is rolled back and fails
+    /// with [`DoltError::MergeConflict`]. Constraint violations don't stop it;
+    /// they are listed in the outcome's report and have to be fixed before
+    /// the merge can be committed.
+    pub fn merge(&mut self, branch: &str) -> Result<MergeOutcome, DoltError> {
+        let outcome = self.merge_keeping_conflicts(branch)?;
+
+        let report = &outcome.report;
+        if !report.conflicts.is_empty() || !report.schema_conflicts.is_empty() {
+            self.abort_merge()?;
+            return Err(DoltError::MergeConflict(branch.to_string()));
+        }
+
+        if report.is_clean() {
+            allow_commit_conflicts(&mut self.conn, false)?;
+        }
+
+        Ok(outcome)
+    }
+
+    /// Merges `branch` into the current branch, leaving any conflicts in the
+    /// working set to be resolved.
+    ///
+    /// The session may commit with unresolved conflicts until
+    /// [`DoltRepo::commit_merge`] or [`DoltRepo::abort_merge`].
+    pub fn merge_keeping_conflicts(&mut self, branch: &str) -> Result<MergeOutcome, DoltError> {
+        allowing_commit_conflicts(&mut self.conn, |conn| {
+            let result = DoltProcedure::new("DOLT_MERGE")
+                .arg(branch)
+                .load::<DoltMergeResponse>(conn)?;
+
+            let merge = result
+                .into_iter()
+                .next()
+                .ok_or_else(|| DoltError::Procedure {
+                    procedure: "DOLT_MERGE".to_string(),
+                    source: diesel::result::Error::NotFound,
+                })?;
+
+            Ok(MergeOutcome {
+                merge,
+                report: merge_report(conn)?,
+            })
+        })
+    }
+
+    /// Conflicts, schema conflicts and constraint violations in the working set.
+    pub fn merge_report(&mut self) -> Result<MergeReport, DoltError> {
+        merge_report(&mut self.conn)
+    }
+
+    /// Checks the constraints of the working set (or of every row with `all`)
+    /// without changing anything.
+    pub fn verify_constraints(&mut self, all: bool) -> Result<Vec<ViolationGroup>, DoltError> {
+        verify_constraints(&mut self.conn, all)
+    }
+
+    /// Tables with unresolved conflicts.
+    pub fn conflicts(&mut self) -> Result<Vec<DoltTableConflicts>, DoltError> {
+        conflicts(&mut self.conn)
+    }
+
+    /// Every unresolved conflict in the table of `C`.
+    pub fn table_conflicts<C: ConflictRow>(&mut self) -> Result<Vec<C>, DoltError> {
+        table_conflicts(&mut self.conn)
+    }
+
+    /// Resolves every conflict in `table` in favour of one side.
+    pub fn resolve_conflicts(&mut self, table: &str, side: ConflictSide) -> Result<(), DoltError> {
+        resolve_conflicts(&mut self.conn, table, side)
+    }
+
+    /// Resolves the conflicts in the table of `C` with `resolve`, one row at a time.
+    pub fn resolve_conflicts_with<C, F>(&mut self, resolve: F) -> Result<usize, DoltError>
+    where
+        C: ConflictRow,
+        F: FnMut(&mut MysqlConnection, &C) -> Result<(), DoltError>,
+    {
+        resolve_conflicts_with(&mut self.conn, resolve)
+    }
+
+    /// Commits a merge once all of its conflicts and violations are resolved.
+    pub fn commit_merge(&mut self, author: &str, message: &str) -> Result<String, DoltError> {
+        let report = self.merge_report()?;
+        if !report.is_clean() {
+            let mut tables: Vec<String> = report
+                .conflicts
+                .into_iter()
+                .map(|c| c.table_name)
+                .chain(report.schema_conflicts.into_iter().map(|c| c.table_name))
+                .chain(report.violations.into_iter().map(|v| v.table_name))
+                .collect();
+            tables.sort();
+            tables.dedup();
+            return Err(DoltError::MergeConflict(tables.join(", ")));
+        }
+
+        let hash = self.commit(author, message)?;
+        allow_commit_conflicts(&mut self.conn, false)?;
+
+        Ok(hash)
+    }
+
+    /// Abandons a merge in progress, conflicts and all.
+    pub fn abort_merge(&mut self) -> Result<(), DoltError> {
+        DoltProcedure::new("DOLT_MERGE")
+            .arg("--abort")
+            .load::<DoltCallResponse>(&mut self.conn)?;
+
+        allow_commit_conflicts(&mut self.conn, false)
+    }
+
+    /// Applies the changes of the commit at `revision` to the current branch.
+    ///
+    /// Conflicts are handled like [`DoltRepo::merge_keeping_conflicts`]:
+    /// resolve them and finish with [`DoltRepo::commit_merge`], or give up
+    /// with [`DoltRepo::abort_cherry_pick`].
+    pub fn cherry_pick(
+        &mut self,
+        revision: impl Into<Revision>,
+    ) -> Result<CherryPickOutcome, DoltError> {
+        let (pick, report) = allowing_commit_conflicts(&mut self.conn, |conn| {
+            let result = DoltProcedure::new("DOLT_CHERRY_PICK")
+                .arg(revision.into().to_arg())
+                .load::<DoltCherryPickResponse>(conn)?;
+
+            let pick = result
+                .into_iter()
+                .next()
+                .ok_or_else(|| DoltError::Procedure {
+                    procedure: "DOLT_CHERRY_PICK".to_string(),
+                    source: diesel::result::Error::NotFound,
+                })?;
+
+            Ok((pick, merge_report(conn)?))
+        })?;
+
+        if report.is_clean() {
+            allow_commit_conflicts(&mut self.conn, false)?;
+        }
+
+        Ok(CherryPickOutcome { pick, report })
+    }
+
+    /// Abandons a cherry-pick that left conflicts behind.
+    pub fn abort_cherry_pick(&mut self) -> Result<(), DoltError> {
+        DoltProcedure::new("DOLT_CHERRY_PICK")
+            .arg("--abort")
+            .load::<DoltCallResponse>(&mut self.conn)?;
+
+        allow_commit_conflicts(&mut self.conn, false)
+    }
+
+    /// Undoes the commit at `revision` with a new commit.
+    ///
+    /// Conflicts are handled like [`DoltRepo::cherry_pick`]: resolve them and
+    /// finish with [`DoltRepo::commit_merge`], or give up with
+    /// [`DoltRepo::abort_revert`].
+    pub fn revert(
+        &mut self,
+        revision: impl Into<Revision>,
+        author: &str,
+    ) -> Result<RevertOutcome, DoltError> {
+        let report = allowing_commit_conflicts(&mut self.conn, |conn| {
+            DoltProcedure::new("DOLT_REVERT")
+                .args([
+                    "--author".to_string(),
+                    author.to_string(),
+                    revision.into().to_arg(),
+                ])
+                .load::<DoltCallResponse>(conn)?;
+
+            merge_report(conn)
+        })?;
+
+        if !report.is_clean() {
+            return Ok(RevertOutcome { hash: None, report });
+        }
+
+        allow_commit_conflicts(&mut self.conn, false)?;
+
+        Ok(RevertOutcome {
+            hash: Some(self.hash_of("HEAD")?),
+            report,
+        })
+    }
+
+    /// Abandons a revert that left conflicts behind.
+    pub fn abort_revert(&mut self) -> Result<(), DoltError> {
+        self.reset_hard(None)?;
+
+        allow_commit_conflicts(&mut self.conn, false)
+    }
+
+    /// Undoes the merge commit at `revision` relative to its `parent`
+    /// (1 for the branch merged into, 2 for the branch merged in), returning
+    /// the new commit's hash.
+    ///
+    /// `DOLT_REVERT` can't pick a parent, so this applies the patch from the
+    /// merge commit back to that parent instead. A patch has no way to detect
+    /// conflicts, so the merge commit has to be HEAD: later commits touching
+    /// the same cells would otherwise be overwritten.
+    ///
+    /// Like [`DoltRepo::apply_patch_file`], this needs a clean working set
+    /// and either commits the revert or leaves everything as it was.
+    pub fn revert_merge(
+        &mut self,
+        revision: impl Into<Revision>,
+        parent: u32,
+        author: &str,
+        message: &str,
+    ) -> Result<String, DoltError> {
+        if parent == 0 {
+            return Err(DoltError::InvalidArgument(
+                "merge parents are numbered from 1".to_string(),
+            ));
+        }
+
+        let merge_hash = self.hash_of(revision)?;
+        if merge_hash != self.hash_of("HEAD")? {
+            return Err(DoltError::InvalidArgument(format!(
+                "only HEAD can be reverted as a merge, not {merge_hash}"
+            )));
+        }
+
+        // dolt_commit_ancestors counts parents from 0. No commit has
+        // anywhere near i32::MAX parents, so that one just won't be found.
+        let parent_index = i32::try_from(parent - 1).unwrap_or(i32::MAX);
+        let parents = dolt_commit_ancestors::table
+            .select(dolt_commit_ancestors::parent_hash)
+            .filter(dolt_commit_ancestors::commit_hash.eq(&merge_hash))
+            .filter(dolt_commit_ancestors::parent_index.eq(parent_index))
+            .load::<String>(&mut self.conn)?;
+
+        let parent_hash = parents.into_iter().next().ok_or_else(|| {
+            DoltError::InvalidArgument(format!("{merge_hash} has no parent {parent}"))
+        })?;
+
+        let statements = patch_statements(&mut self.conn, merge_hash, parent_hash)?;
+
+        self.conn.transaction::<_, DoltError, _>(|conn| {
+            ensure_clean(conn)?;
+            apply_statements(conn, &statements)?;
+
+            commit(conn, author, message)
+        })
+    }
+
+    /// The commit hash `revision` resolves to.
+    pub fn hash_of(&mut self, revision: impl Into<Revision>) -> Result<String, DoltError> {
+        hash_of(&mut self.conn, revision)
+    }
+
+    /// Every commit of the database with its parents.
+    pub fn commit_graph(&mut self) -> Result<CommitGraph, DoltError> {
+        CommitGraph::load(&mut self.conn)
+    }
+
+    /// The best common ancestor of `left` and `right`.
+    pub fn merge_base(
+        &mut self,
+        left: impl Into<Revision>,
+        right: impl Into<Revision>,
+    ) -> Result<String, DoltError> {
+        merge_base(&mut self.conn, left, right)
+    }
+
+    /// Whether `descendant` is `ancestor` or descends from it.
+    pub fn is_ancestor(
+        &mut self,
+        ancestor: impl Into<Revision>,
+        descendant: impl Into<Revision>,
+    ) -> Result<bool, DoltError> {
+        is_ancestor(&mut self.conn, ancestor, descendant)
+    }
+
+    /// How many commits this branch has that `upstream` doesn't, and the
+    /// other way around.
+    pub fn ahead_behind(
+        &mut self,
+        upstream: impl Into<Revision>,
+    ) -> Result<(usize, usize), DoltError> {
+        ahead_behind(&mut self.conn, self.branch.as_str(), upstream)
+    }
+
+    /// Starts an interactive rebase of this branch onto `upstream` and
+    /// returns the plan.
+    pub fn start_rebase(&mut self, upstream: &str) -> Result<Vec<RebaseStep>, DoltError> {
+        start_rebase(&mut self.conn, &self.database, &self.branch, upstream)
+    }
+
+    /// The plan of the rebase in progress.
+    pub fn rebase_plan(&mut self) -> Result<Vec<RebaseStep>, DoltError> {
+        rebase_plan(&mut self.conn)
+    }
+
+    /// Writes an edited step back to the plan.
+    pub fn update_rebase_step(&mut self, step: &RebaseStep) -> Result<(), DoltError> {
+        update_rebase_step(&mut self.conn, step)
+    }
+
+    /// Runs the plan.
+    pub fn continue_rebase(&mut self) -> Result<(), DoltError> {
+        continue_rebase(&mut self.conn, &self.database, &self.branch)
+    }
+
+    /// Abandons the rebase, leaving the branch as it was.
+    pub fn abort_rebase(&mut self) -> Result<(), DoltError> {
+        abort_rebase(&mut self.conn, &self.database, &self.branch)
+    }
+
+    /// Discards the working set, moving HEAD to `commit` when one is given.
+    ///
+    /// `commit` can be anything Dolt resolves to a commit: a hash, a branch or a tag.
+    pub fn reset_hard(&mut self, commit: Option<&str>) -> Result<(), DoltError> {
+        let mut procedure = DoltProcedure::new("DOLT_RESET").arg("--hard");
+        if let Some(commit_hash) = commit {
+            procedure = procedure.arg(commit_hash);
+        }
+
+        self.conn.transaction::<_, DoltError, _>(|conn| {
+            procedure.load::<DoltCallResponse>(conn)?;
+
+            Ok(())
+        })
+    }
+
+    /// The commit log of the current branch, newest first.
+    pub fn log(&mut self) -> Result<Vec<DoltLogEntry>, DoltError> {
+        Ok(dolt_log::table
+            .select(DoltLogEntry::as_select())
+            .order(dolt_log::date.desc())
+            .load::<DoltLogEntry>(&mut self.conn)?)
+    }
+
+    /// Tables with uncommitted changes.
+    pub fn status(&mut self) -> Result<Vec<DoltStatus>, DoltError> {
+        Ok(dolt_status::table
+            .select(DoltStatus::as_select())
+            .load::<DoltStatus>(&mut self.conn)?)
+    }
+
+    /// The versions of one row across the commits of the current branch, oldest first.
+    pub fn history_of<M: DoltHistory>(
+        &mut self,
+        id: M::Id,
+    ) -> Result<Vec<HistoryEntry<M>>, DoltError> {
+        history_of(&mut self.conn, id)
+    }
+
+    /// The commit that last changed each row of `table`, from `dolt_blame_<table>`.
+    pub fn blame<T>(&mut self, table: &str) -> Result<Vec<T>, DoltError>
+    where
+        T: QueryableByName<Mysql> + 'static,
+    {
+        blame(&mut self.conn, table)
+    }
+
+    /// The commit that last changed each field of the row with the given id.
+    pub fn column_blame<M>(&mut self, id: M::Id) -> Result<Vec<ColumnBlame>, DoltError>
+    where
+        M: DoltHistory + ColumnValues,
+    {
+        column_blame::<M>(&mut self.conn, id)
+    }
+
+    /// Working set changes to `table`, loaded into a `dolt_diff_<table>` row type.
+    pub fn diff<T>(&mut self, table: &str) -> Result<Vec<T>, DoltError>
+    where
+        T: QueryableByName<Mysql> + 'static,
+    {
+        // Show only working set changes
+        let stmt = format!(
+            "SELECT * FROM {} WHERE to_commit = 'WORKING'",
+            quote_identifier(&format!("dolt_diff_{table}"))
+        );
+
+        sql_query(stmt)
+            .load::<T>(&mut self.conn)
+            .map_err(|e| DoltError::from_table_query(table, e))
+    }
+
+    /// Changes to `table` going from `from` to `to`.
+    pub fn diff_between<T>(
+        &mut self,
+        from: impl Into<Revision>,
+        to: impl Into<Revision>,
+        table: &str,
+    ) -> Result<Vec<T>, DoltError>
+    where
+        T: QueryableByName<Mysql> + 'static,
+    {
+        diff_between(&mut self.conn, from, to, table)
+    }
+
+    /// Writes the SQL patch from `from` to `to` to the file at `path`.
+    pub fn write_patch_file(
+        &mut self,
+        from: impl Into<Revision>,
+        to: impl Into<Revision>,
+        path: impl AsRef<Path>,
+    ) -> Result<usize, DoltError> {
+        write_patch_file(&mut self.conn, from, to, path)
+    }
+
+    /// Runs the `.sql` file at `path` on this branch and commits it, leaving
+    /// the working set untouched if anything fails.
+    pub fn apply_patch_file(
+        &mut self,
+        path: impl AsRef<Path>,
+        author: &str,
+        message: &str,
+    ) -> Result<String, DoltError> {
+        apply_patch_file(&mut self.conn, path, author, message)
+    }
+
+    /// The columns each commit changed, per commit and table, newest first.
+    pub fn column_changes(
+        &mut self,
+        filter: &ColumnDiffFilter,
+    ) -> Result<Vec<ColumnChanges>, DoltError> {
+        column_changes(&mut self.conn, filter)
+    }
+
+    /// Every change to the columns matching `filter`, oldest first.
+    pub fn column_history(
+        &mut self,
+        filter: &ColumnDiffFilter,
+    ) -> Result<Vec<DoltColumnDiff>, DoltError> {
+        column_history(&mut self.conn, filter)
+    }
+
+    /// Per-table row and cell counts of the changes from `from` to `to`.
+    pub fn diff_stat(
+        &mut self,
+        from: impl Into<Revision>,
+        to: impl Into<Revision>,
+        table: Option<&str>,
+    ) -> Result<Vec<DoltDiffStat>, DoltError> {
+        diff_stat(&mut self.conn, from, to, table)
+    }
+
+    /// Which tables changed from `from` to `to`, in their data or schema.
+    pub fn diff_summary(
+        &mut self,
+        from: impl Into<Revision>,
+        to: impl Into<Revision>,
+        table: Option<&str>,
+    ) -> Result<Vec<DoltDiffSummary>, DoltError> {
+        diff_summary(&mut self.conn, from, to, table)
+    }
+
+    /// The `CREATE TABLE` statements of the tables whose schema changed
+    /// from `from` to `to`.
+    pub fn schema_diff(
+        &mut self,
+        from: impl Into<Revision>,
+        to: impl Into<Revision>,
+        table: Option<&str>,
+    ) -> Result<Vec<DoltSchemaDiff>, DoltError> {
+        schema_diff(&mut self.conn, from, to, table)
+    }
+
+    /// Changes to `table` on `to` since it diverged from `from` (a three-dot diff).
+    pub fn diff_from_merge_base<T>(
+        &mut self,
+        from: impl Into<Revision>,
+        to: impl Into<Revision>,
+        table: &str,
+    ) -> Result<Vec<T>, DoltError>
+    where
+        T: QueryableByName<Mysql> + 'static,
+    {
+        diff_from_merge_base(&mut self.conn, from, to, table)
+    }
+}
+
+/// Stages every change and commits it, returning the new commit's hash.
+pub fn commit(
+    conn: &mut MysqlConnection,
+    author: &str,
+    message: &str,
+) -> Result<String, DoltError> {
+    DoltProcedure::new("DOLT_ADD")
+        .arg("-A")
+        .load::<DoltCallResponse>(conn)?;
+
+    // Without '--skip-empty' Dolt tells us when there is nothing to commit.
+    let result_commit = DoltProcedure::new("DOLT_COMMIT")
+        .args(["--author", author, "-m", message])
+        .load::<DoltCommitResponse>(conn)?;
+
+    match result_commit.into_iter().next() {
+        Some(commit) => Ok(commit.hash),
+        None => Err(DoltError::NothingToCommit),
+    }
+}
+
+/// Asks the server for the session's active branch.
+pub fn active_branch(conn: &mut MysqlConnection) -> Result<String, DoltError> {
+    let stmt = "SELECT active_branch()";
+    let result: Vec<ActiveBranch> = sql_query(stmt).load(conn)?;
+
+    result
+        .into_iter()
+        .next()
+        .map(|active_branch| active_branch.name)
+        .ok_or(DoltError::Query(diesel::result::Error::NotFound))
+}
@@ -0,0 +1,605 @@
+//! Schema changes between revisions, from the `DOLT_SCHEMA_DIFF()` table
+//! function.
+//!
+//! Each changed table comes with its `CREATE TABLE` statement on both sides.
+//! [`DoltSchemaDiff::changes`] breaks that down into columns, keys and
+//! constraints, [`DoltSchemaDiff::unified_diff`] renders it like `diff -u`:
+//!
+//! ```no_run
+//! # use dolt_dieselrs_getting_started::{DoltError, DoltRepo};
+//! # fn run(repo: &mut DoltRepo) -> Result<(), DoltError> {
+//! for table in repo.schema_diff("main", "modify_schema", None)? {
+//!     for change in table.changes() {
+//!         println!("{change}");
+//!     }
+//!     print!("{}", table.unified_diff(3));
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+
+use diesel::{mysql::Mysql, sql_query, sql_types::Text, MysqlConnection, RunQueryDsl};
+
+use crate::{as_of::Revision, error::DoltError, models::DoltSchemaDiff};
+
+/// The schema changes going from `from` to `to`, for every changed table or
+/// just `table`.
+pub fn schema_diff(
+    conn: &mut MysqlConnection,
+    from: impl Into<Revision>,
+    to: impl Into<Revision>,
+    table: Option<&str>,
+) -> Result<Vec<DoltSchemaDiff>, DoltError> {
+    let args = if table.is_some() { "?, ?, ?" } else { "?, ?" };
+    let stmt = format!(
+        "SELECT from_table_name, to_table_name, from_create_statement, to_create_statement \
+         FROM DOLT_SCHEMA_DIFF({args})"
+    );
+
+    let mut query = sql_query(stmt)
+        .into_boxed::<Mysql>()
+        .bind::<Text, _>(from.into().to_arg())
+        .bind::<Text, _>(to.into().to_arg());
+    if let Some(table) = table {
+        query = query.bind::<Text, _>(table);
+    }
+
+    query
+        .load::<DoltSchemaDiff>(conn)
+        .map_err(|e| DoltError::from_table_query(table.unwrap_or("DOLT_SCHEMA_DIFF"), e))
+}
+
+/// What part of a table a [`SchemaChange`] is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaElement {
+    Column,
+    PrimaryKey,
+    Index,
+    ForeignKey,
+    Check,
+}
+
+impl fmt::Display for SchemaElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SchemaElement::Column => "column",
+            SchemaElement::PrimaryKey => "primary key",
+            SchemaElement::Index => "index",
+            SchemaElement::ForeignKey => "foreign key",
+            SchemaElement::Check => "check",
+        })
+    }
+}
+
+/// One column, key or constraint that was added, dropped or changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaChange {
+    pub element: SchemaElement,
+    pub name: String,
+    /// The definition before the change, missing when it was added.
+    pub from: Option<String>,
+    /// The definition after the change, missing when it was dropped.
+    pub to: Option<String>,
+}
+
+impl SchemaChange {
+    /// For a changed column, whether its type changed rather than only its
+    /// default, nullability or other attributes.
+    pub fn type_changed(&self) -> bool {
+        let column_type =
+            |definition: &str| definition.split_whitespace().next().map(str::to_lowercase);
+
+        match (self.element, &self.from, &self.to) {
+            (SchemaElement::Column, Some(from), Some(to)) => column_type(from) != column_type(to),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.from, &self.to) {
+            (None, Some(to)) => write!(f, "+ {} {}: {to}", self.element, self.name),
+            (Some(from), None) => write!(f, "- {} {}: {from}", self.element, self.name),
+            (Some(from), Some(to)) => {
+                write!(f, "~ {} {}: {from} -> {to}", self.element, self.name)
+            }
+            (None, None) => write!(f, "  {} {}", self.element, self.name),
+        }
+    }
+}
+
+impl DoltSchemaDiff {
+    /// The table's name before the change, `None` if it was created.
+    pub fn from_table(&self) -> Option<&str> {
+        Some(self.from_table_name.as_str()).filter(|name| !name.is_empty())
+    }
+
+    /// The table's name after the change, `None` if it was dropped.
+    pub fn to_table(&self) -> Option<&str> {
+        Some(self.to_table_name.as_str()).filter(|name| !name.is_empty())
+    }
+
+    /// The columns, keys and constraints that differ, in the order they
+    /// appear in the `CREATE TABLE` statements.
+    pub fn changes(&self) -> Vec<SchemaChange> {
+        let from = TableDefinition::parse(&self.from_create_statement);
+        let to = TableDefinition::parse(&self.to_create_statement);
+        let mut changes = Vec::new();
+
+        for (element, name, definition) in &from.elements {
+            match to.find(*element, name) {
+                Some(to_definition) if to_definition == definition => {}
+                to_definition => changes.push(SchemaChange {
+                    element: *element,
+                    name: name.clone(),
+                    from: Some(definition.clone()),
+                    to: to_definition.cloned(),
+                }),
+            }
+        }
+        for (element, name, definition) in &to.elements {
+            if from.find(*element, name).is_none() {
+                changes.push(SchemaChange {
+                    element: *element,
+                    name: name.clone(),
+                    from: None,
+                    to: Some(definition.clone()),
+                });
+            }
+        }
+
+        changes
+    }
+
+    /// The two `CREATE TABLE` statements as a unified diff with `context`
+    /// unchanged lines around each change.
+    pub fn unified_diff(&self, context: usize) -> String {
+        unified_diff(
+            self.from_table().unwrap_or("/dev/null"),
+            self.to_table().unwrap_or("/dev/null"),
+            &self.from_create_statement,
+            &self.to_create_statement,
+            context,
+        )
+    }
+}
+
+/// The parts of a `CREATE TABLE` statement, each as (element, name, definition).
+struct TableDefinition {
+    elements: Vec<(SchemaElement, String, String)>,
+}
+
+impl TableDefinition {
+    fn parse(create_statement: &str) -> Self {
+        let body = match (create_statement.find('('), create_statement.rfind(')')) {
+            (Some(start), Some(end)) if start < end => &create_statement[start + 1..end],
+            _ => "",
+        };
+
+        let elements = split_top_level(body)
+            .into_iter()
+            .filter_map(|line| Self::parse_line(&line))
+            .collect();
+
+        TableDefinition { elements }
+    }
+
+    fn parse_line(line: &str) -> Option<(SchemaElement, String, String)> {
+        let upper = line.to_uppercase();
+
+        if line.starts_with('`') {
+            let (name, definition) = quoted_name(line)?;
+            return Some((SchemaElement::Column, name, definition.trim().to_string()));
+        }
+        if upper.starts_with("PRIMARY KEY") {
+            return Some((
+                SchemaElement::PrimaryKey,
+                "PRIMARY".to_string(),
+                line["PRIMARY KEY".len()..].trim().to_string(),
+            ));
+        }
+        if upper.starts_with("CONSTRAINT") {
+            let (name, definition) = quoted_name(line["CONSTRAINT".len()..].trim_start())?;
+            let element = if definition.trim_start().to_uppercase().starts_with("CHECK") {
+                SchemaElement::Check
+            } else {
+                SchemaElement::ForeignKey
+            };
+            return Some((element, name, definition.trim().to_string()));
+        }
+        if upper.starts_with("FOREIGN KEY") || upper.starts_with("CHECK") {
+            // Unnamed, so the definition has to stand in for the name.
+            let element = if upper.starts_with("CHECK") {
+                SchemaElement::Check
+            } else {
+                SchemaElement::ForeignKey
+            };
+            return Some((element, line.to_string(), line.to_string()));
+        }
+        if let Some(start) = line.find('`') {
+            // KEY, UNIQUE KEY, FULLTEXT KEY, SPATIAL KEY and INDEX
+            let (name, rest) = quoted_name(&line[start..])?;
+            let definition = format!("{}{}", line[..start].trim_end(), rest);
+            return Some((SchemaElement::Index, name, definition));
+        }
+
+        None
+    }
+
+    fn find(&self, element: SchemaElement, name: &str) -> Option<&String> {
+        self.elements
+            .iter()
+            .find(|(e, n, _)| *e == element && n == name)
+            .map(|(_, _, definition)| definition)
+    }
+}
+
+/// Splits a leading `` `name` `` off `text`, returning the unquoted name and the rest.
+fn quoted_name(text: &str) -> Option<(String, &str)> {
+    let rest = text.strip_prefix('`')?;
+    let mut name = String::new();
+    let mut chars = rest.char_indices().peekable();
+
+    while let Some((at, c)) = chars.next() {
+        if c == '`' {
+            // Backticks inside a name are doubled.
+            if chars.peek().map(|(_, next)| *next) == Some('`') {
+                chars.next();
+                name.push('`');
+                continue;
+            }
+            return Some((name, &rest[at + 1..]));
+        }
+        name.push(c);
+    }
+
+    None
+}
+
+/// Splits on the commas that aren't inside parentheses or quotes.
+fn split_top_level(body: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+
+    for c in body.chars() {
+        match quote {
+            Some(q) => {
+                if c == q {
+                    quote = None;
+                }
+            }
+            None => match c {
+                '\'' | '"' | '`' => quote = Some(c),
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                ',' if depth == 0 => {
+                    parts.push(current.trim().to_string());
+                    current.clear();
+                    continue;
+                }
+                _ => {}
+            },
+        }
+        current.push(c);
+    }
+
+    if !current.trim().is_empty() {
+        parts.push(current.trim().to_string());
+    }
+
+    parts
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Line<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// A unified diff of `from` and `to`, line by line, like `diff -u`.
+pub fn unified_diff(
+    from_name: &str,
+    to_name: &str,
+    from: &str,
+    to: &str,
+    context: usize,
+) -> String {
+    let lines = diff_lines(from, to);
+    if lines.iter().all(|line| matches!(line, Line::Same(_))) {
+        return String::new();
+    }
+
+    let mut output = format!("--- {from_name}\n+++ {to_name}\n");
+
+    /*
+    Every change brings `context` lines on either side. Changes whose
+    context would touch or overlap share a hunk.
+    */
+
+    let changed: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Line::Same(_)))
+        .map(|(index, _)| index)
+        .collect();
+
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for index in changed {
+        let start = index.saturating_sub(context);
+        let end = (index + context + 1).min(lines.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    for (start, end) in hunks {
+        // Line numbers of the hunk's first line on either side, counting from 1.
+        let from_start = 1 + lines[..start]
+            .iter()
+            .filter(|line| !matches!(line, Line::Added(_)))
+            .count();
+        let to_start = 1 + lines[..start]
+            .iter()
+            .filter(|line| !matches!(line, Line::Removed(_)))
+            .count();
+        let hunk = &lines[start..end];
+        let from_count = hunk
+            .iter()
+            .filter(|line| !matches!(line, Line::Added(_)))
+            .count();
+        let to_count = hunk
+            .iter()
+            .filter(|line| !matches!(line, Line::Removed(_)))
+            .count();
+
+        output.push_str(&format!(
+            "@@ -{},{from_count} +{},{to_count} @@\n",
+            if from_count == 0 {
+                from_start - 1
+            } else {
+                from_start
+            },
+            if to_count == 0 {
+                to_start - 1
+            } else {
+                to_start
+            },
+        ));
+        for line in hunk {
+            let (marker, text) = match line {
+                Line::Same(text) => (' ', text),
+                Line::Removed(text) => ('-', text),
+                Line::Added(text) => ('+', text),
+            };
+            output.push(marker);
+            output.push_str(text);
+            output.push('\n');
+        }
+    }
+
+    output
+}
+
+/// The lines of `from` and `to` aligned on their longest common subsequence.
+fn diff_lines<'a>(from: &'a str, to: &'a str) -> Vec<Line<'a>> {
+    let from: Vec<&str> = from.lines().collect();
+    let to: Vec<&str> = to.lines().collect();
+
+    // lengths[i][j] is the LCS length of from[i..] and to[j..].
+    let mut lengths = vec![vec![0usize; to.len() + 1]; from.len() + 1];
+    for i in (0..from.len()).rev() {
+        for j in (0..to.len()).rev() {
+            lengths[i][j] = if from[i] == to[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::with_capacity(from.len().max(to.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < from.len() && j < to.len() {
+        if from[i] == to[j] {
+            lines.push(Line::Same(from[i]));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            lines.push(Line::Removed(from[i]));
+            i += 1;
+        } else {
+            lines.push(Line::Added(to[j]));
+            j += 1;
+        }
+    }
+    lines.extend(from[i..].iter().map(|line| Line::Removed(line)));
+    lines.extend(to[j..].iter().map(|line| Line::Added(line)));
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FROM: &str = "CREATE TABLE `employees` (
+  `id` int NOT NULL,
+  `last_name` varchar(255),
+  `first_name` varchar(255),
+  `nick,(name)` varchar(10) DEFAULT 'a,b',
+  PRIMARY KEY (`id`),
+  KEY `name_idx` (`last_name`,`first_name`),
+  CONSTRAINT `fk_team` FOREIGN KEY (`team_id`) REFERENCES `teams` (`id`),
+  CONSTRAINT `chk_id` CHECK ((`id` >= 0))
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4";
+
+    const TO: &str = "CREATE TABLE `employees` (
+  `id` bigint NOT NULL,
+  `last_name` varchar(255) NOT NULL,
+  `first_name` varchar(255),
+  `start_date` datetime,
+  PRIMARY KEY (`id`),
+  KEY `name_idx` (`first_name`),
+  CONSTRAINT `fk_team` FOREIGN KEY (`team_id`) REFERENCES `teams` (`id`),
+  CONSTRAINT `chk_positive` CHECK ((`id` > 0))
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4";
+
+    fn schema_diff(from: &str, to: &str) -> DoltSchemaDiff {
+        DoltSchemaDiff {
+            from_table_name: if from.is_empty() { "" } else { "employees" }.to_string(),
+            to_table_name: if to.is_empty() { "" } else { "employees" }.to_string(),
+            from_create_statement: from.to_string(),
+            to_create_statement: to.to_string(),
+        }
+    }
+
+    fn change(
+        element: SchemaElement,
+        name: &str,
+        from: Option<&str>,
+        to: Option<&str>,
+    ) -> SchemaChange {
+        SchemaChange {
+            element,
+            name: name.to_string(),
+            from: from.map(str::to_string),
+            to: to.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn lists_changes_in_statement_order() {
+        use SchemaElement::*;
+
+        assert_eq!(
+            schema_diff(FROM, TO).changes(),
+            [
+                change(Column, "id", Some("int NOT NULL"), Some("bigint NOT NULL")),
+                change(
+                    Column,
+                    "last_name",
+                    Some("varchar(255)"),
+                    Some("varchar(255) NOT NULL")
+                ),
+                change(
+                    Column,
+                    "nick,(name)",
+                    Some("varchar(10) DEFAULT 'a,b'"),
+                    None
+                ),
+                change(
+                    Index,
+                    "name_idx",
+                    Some("KEY (`last_name`,`first_name`)"),
+                    Some("KEY (`first_name`)")
+                ),
+                change(Check, "chk_id", Some("CHECK ((`id` >= 0))"), None),
+                change(Column, "start_date", None, Some("datetime")),
+                change(Check, "chk_positive", None, Some("CHECK ((`id` > 0))")),
+            ]
+        );
+    }
+
+    #[test]
+    fn tells_type_changes_from_attribute_changes() {
+        let changes = schema_diff(FROM, TO).changes();
+
+        assert!(changes[0].type_changed());
+        assert!(!changes[1].type_changed());
+        // Only changed columns have a type change.
+        assert!(!changes[2].type_changed());
+        assert!(!changes[3].type_changed());
+    }
+
+    #[test]
+    fn parses_keys_and_constraints() {
+        use SchemaElement::*;
+
+        let table = TableDefinition::parse(
+            "CREATE TABLE `t` (
+  `id` int,
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `u` (`id`),
+  CONSTRAINT `fk` FOREIGN KEY (`id`) REFERENCES `p` (`id`) ON DELETE CASCADE,
+  CHECK ((`id` > 0))
+)",
+        );
+
+        assert_eq!(
+            table.elements,
+            [
+                (Column, "id".to_string(), "int".to_string()),
+                (PrimaryKey, "PRIMARY".to_string(), "(`id`)".to_string()),
+                (Index, "u".to_string(), "UNIQUE KEY (`id`)".to_string()),
+                (
+                    ForeignKey,
+                    "fk".to_string(),
+                    "FOREIGN KEY (`id`) REFERENCES `p` (`id`) ON DELETE CASCADE".to_string()
+                ),
+                (
+                    Check,
+                    "CHECK ((`id` > 0))".to_string(),
+                    "CHECK ((`id` > 0))".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn splits_only_top_level_commas() {
+        assert_eq!(
+            split_top_level("`a,b` int, `c` decimal(10,2) DEFAULT '1,2', KEY `k` (`a,b`,`c`)"),
+            [
+                "`a,b` int",
+                "`c` decimal(10,2) DEFAULT '1,2'",
+                "KEY `k` (`a,b`,`c`)"
+            ]
+        );
+    }
+
+    #[test]
+    fn unquotes_names() {
+        assert_eq!(
+            quoted_name("`a``b(c)` int"),
+            Some(("a`b(c)".to_string(), " int"))
+        );
+        assert_eq!(quoted_name("`unterminated"), None);
+        assert_eq!(quoted_name("plain"), None);
+    }
+
+    #[test]
+    fn groups_nearby_changes_into_hunks() {
+        let from = "a\nb\nc\nd\ne\nf\ng\nh\ni";
+        let to = "a\nB\nc\nd\ne\nf\ng\nH\ni";
+
+        assert_eq!(
+            unified_diff("t", "t", from, to, 1),
+            "--- t\n+++ t\n\
+             @@ -1,3 +1,3 @@\n a\n-b\n+B\n c\n\
+             @@ -7,3 +7,3 @@\n g\n-h\n+H\n i\n"
+        );
+        assert_eq!(
+            unified_diff("t", "t", from, to, 3),
+            "--- t\n+++ t\n\
+             @@ -1,9 +1,9 @@\n a\n-b\n+B\n c\n d\n e\n f\n g\n-h\n+H\n i\n"
+        );
+    }
+
+    #[test]
+    fn diffs_created_and_unchanged_tables() {
+        assert_eq!(
+            schema_diff("", "CREATE TABLE `t` (\n  `id` int\n)").unified_diff(3),
+            "--- /dev/null\n+++ employees\n\
+             @@ -0,0 +1,3 @@\n+CREATE TABLE `t` (\n+  `id` int\n+)\n"
+        );
+        assert_eq!(schema_diff(FROM, FROM).unified_diff(3), "");
+        assert!(schema_diff(FROM, FROM).changes().is_empty());
+    }
+}